@@ -16,7 +16,7 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
- use std::{fs::File, io::Write, sync::{Arc, Mutex, mpsc::{Receiver, Sender}}, time::Duration, usize};
+ use std::{collections::VecDeque, fs::File, io::Write, sync::{Arc, Mutex, mpsc::{Receiver, Sender}}, time::Duration, usize};
 
 use rand::{Rng, prelude::ThreadRng};
 
@@ -24,8 +24,14 @@ use self::display::Change;
 
 mod memory;
 mod timer;
+mod cache;
+pub mod audio;
+pub mod debugger;
 pub mod display;
 pub mod keyboard;
+pub mod snapshot;
+
+use snapshot::MachineState;
 
 // types for parameters
 /// A 12-Bit address
@@ -40,7 +46,7 @@ type X = u8;
 type Y = u8;
 
 /// All available instruction types for the chip-8 cpu
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Instruction {
     /// Calls machine code routine
     Call(NNN),
@@ -114,6 +120,28 @@ pub enum Instruction {
     RegLoad(X),
     /// No operation
     StopExecution,
+    /// SUPER-CHIP: scroll the display down by N pixel rows (`00Cn`)
+    ScrollDown(N),
+    /// SUPER-CHIP: scroll the display 4 pixels right (`00FB`)
+    ScrollRight,
+    /// SUPER-CHIP: scroll the display 4 pixels left (`00FC`)
+    ScrollLeft,
+    /// SUPER-CHIP: exit the interpreter (`00FD`)
+    Exit,
+    /// SUPER-CHIP: switch to 64x32 low-res mode (`00FE`)
+    LowRes,
+    /// SUPER-CHIP: switch to 128x64 hi-res mode (`00FF`)
+    HighRes,
+    /// SUPER-CHIP: point I at the large (10-byte) hex digit glyph for Vx (`Fx30`)
+    PointLargeChar(X),
+    /// SUPER-CHIP: save V0..Vx to the HP48 flag registers (`Fx75`)
+    FlagSave(X),
+    /// SUPER-CHIP: load V0..Vx from the HP48 flag registers (`Fx85`)
+    FlagLoad(X),
+    /// XO-CHIP: set the pitch register from Vx (`Fx3A`)
+    PitchSet(X),
+    /// XO-CHIP: load the 16-byte audio pattern buffer from memory at I (`F002`)
+    AudioLoad,
 }
 
 // Constants
@@ -149,6 +177,83 @@ const DEFAULT_FONTPACK: [u8; 80] = [
 /// The default font location
 const DEFAULT_FONTPACK_LOCATION: usize = 0x50;
 
+/// SUPER-CHIP's large (10-byte-per-digit) hex font, used by `Fx30`. Only
+/// digits 0-9 have a standardized large glyph; the HP48-derived spec never
+/// defined A-F ones, so registers holding those values point past the end
+/// of this table, at whatever follows it in RAM.
+const LARGE_FONTPACK: [u8; 100] = [
+    0x3C, 0x7E, 0xE7, 0xC3, 0xC3, 0xC3, 0xC3, 0xE7, 0x7E, 0x3C, // 0
+    0x18, 0x38, 0x58, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x3C, // 1
+    0x3E, 0x7F, 0xC3, 0x06, 0x0C, 0x18, 0x30, 0x60, 0xFF, 0xFF, // 2
+    0x3C, 0x7E, 0xC3, 0x03, 0x0E, 0x0E, 0x03, 0xC3, 0x7E, 0x3C, // 3
+    0x06, 0x0E, 0x1E, 0x36, 0x66, 0xC6, 0xFF, 0xFF, 0x06, 0x06, // 4
+    0xFF, 0xFF, 0xC0, 0xC0, 0xFC, 0xFE, 0x03, 0xC3, 0x7E, 0x3C, // 5
+    0x3E, 0x7C, 0xC0, 0xC0, 0xFC, 0xFE, 0xC3, 0xC3, 0x7E, 0x3C, // 6
+    0xFF, 0xFF, 0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x60, 0x60, // 7
+    0x3C, 0x7E, 0xC3, 0xC3, 0x7E, 0x7E, 0xC3, 0xC3, 0x7E, 0x3C, // 8
+    0x3C, 0x7E, 0xC3, 0xC3, 0x7F, 0x3F, 0x03, 0x03, 0x7E, 0x3C, // 9
+];
+
+/// The large font location, placed directly after [`DEFAULT_FONTPACK`].
+const LARGE_FONTPACK_LOCATION: usize = DEFAULT_FONTPACK_LOCATION + DEFAULT_FONTPACK.len();
+
+/// How many executed instructions elapse between automatic rewind snapshots.
+const REWIND_INTERVAL: u64 = EXEC_SPEED;
+/// How many rewind snapshots [`Chip8Emu::run`] keeps around, i.e. how many
+/// times [`REWIND_INTERVAL`] worth of instructions [`Chip8Emu::rewind`] can step back.
+const REWIND_CAPACITY: usize = 60;
+
+/// Resolves CHIP-8's well-known ambiguous instructions to one interpreter
+/// lineage's behavior or another, selected per-ROM via [`Chip8Emu::new`].
+#[derive(Debug, Clone, Copy)]
+pub struct Quirks {
+    /// `true`: `BitShiftR`/`BitShiftL` shift Vx in place, ignoring Vy (CHIP-48/SUPER-CHIP).
+    /// `false`: Vx is first set to Vy, then shifted (COSMAC VIP).
+    pub shift: bool,
+    /// `true`: `RegDmp`/`RegLoad` leave `I` unchanged (SUPER-CHIP).
+    /// `false`: `I` ends up pointing past the last register touched (COSMAC VIP).
+    pub load_store: bool,
+    /// `true`: `FlowJmpV0` computes `XNN + Vx` (SUPER-CHIP's `BXNN`).
+    /// `false`: it computes `NNN + V0` (the original `Bnnn`).
+    pub jump: bool,
+    /// `true`: `BitOr`/`BitAnd`/`BitXor` reset VF to 0 (COSMAC VIP).
+    /// `false`: VF is left untouched.
+    pub logic: bool,
+    /// `true`: sprites clip at the screen edge. `false`: they wrap around to
+    /// the opposite edge.
+    pub clip: bool,
+}
+
+impl Quirks {
+    /// The original COSMAC VIP interpreter's behavior.
+    pub fn cosmac_vip() -> Self {
+        Self {
+            shift: false,
+            load_store: false,
+            jump: false,
+            logic: true,
+            clip: false,
+        }
+    }
+
+    /// CHIP-48/SUPER-CHIP's behavior, matching most modern interpreters.
+    pub fn schip() -> Self {
+        Self {
+            shift: true,
+            load_store: true,
+            jump: true,
+            logic: false,
+            clip: true,
+        }
+    }
+}
+
+impl Default for Quirks {
+    fn default() -> Self {
+        Self::schip()
+    }
+}
+
 /// CPU
 pub struct Chip8Emu {
     /// RAM
@@ -169,6 +274,22 @@ pub struct Chip8Emu {
     reg: [u8; 0x10],
     /// Address pointer
     i: u16,
+    /// SUPER-CHIP's 8 HP48-style persistent flag registers (`Fx75`/`Fx85`)
+    flags: [u8; 8],
+    /// Which interpretation of CHIP-8's ambiguous instructions to follow
+    quirks: Quirks,
+    /// XO-CHIP's 128-bit (16-byte) audio waveform pattern buffer (`F002`)
+    pattern: [u8; 16],
+    /// XO-CHIP's audio pitch register, controlling playback rate (`Fx3A`)
+    pitch: u8,
+    /// Predecoded instructions, populated lazily and invalidated on writes
+    /// to the addresses they were decoded from. `None` when disabled via
+    /// [`Chip8Emu::new`]'s `cache` flag, falling back to decoding fresh on
+    /// every fetch.
+    instruction_cache: Option<cache::InstructionCache>,
+    /// Ring buffer of automatic snapshots taken every [`REWIND_INTERVAL`]
+    /// instructions during `run`, oldest-first, capped at [`REWIND_CAPACITY`].
+    rewind_buffer: VecDeque<MachineState>,
     /// Log
     #[cfg(debug_assertions)]
     log: File,
@@ -176,11 +297,14 @@ pub struct Chip8Emu {
 
 /// The main emulator
 impl Chip8Emu {
-    /// Instanciate the emulator
-    pub fn new(rom: [u8; 0xE00]) -> Self {
+    /// Instanciate the emulator. `cache` enables the predecoded instruction
+    /// cache; disable it (e.g. under [`debugger::Debugger`]) to always
+    /// decode straight from RAM.
+    pub fn new(rom: [u8; 0xE00], quirks: Quirks, cache: bool) -> Self {
         let mut ram = memory::Memory::new();
 
         ram.load(&DEFAULT_FONTPACK, DEFAULT_FONTPACK_LOCATION);
+        ram.load(&LARGE_FONTPACK, LARGE_FONTPACK_LOCATION);
         ram.load(&rom, 0x200);
 
         Self {
@@ -193,34 +317,167 @@ impl Chip8Emu {
             sp: 0x0, // Stack pointer
             reg: [0x0; 0x10], // Registers V0 to VF (VF is the status flag register)
             i: 0x0, // Address pointer
+            flags: [0x0; 8], // HP48-style flag registers
+            quirks, // Ambiguous instruction behavior
+            pattern: [0x0; 16], // XO-CHIP audio pattern buffer
+            pitch: 0x0, // XO-CHIP pitch register
+            instruction_cache: cache.then(|| cache::InstructionCache::new(0x1000)),
+            rewind_buffer: VecDeque::with_capacity(REWIND_CAPACITY),
             #[cfg(debug_assertions)]
             log: File::create("emu.log").unwrap(),
         }
     }
 
+    /// Switches the display between 64x32 (the default) and SUPER-CHIP's
+    /// 128x64 hi-res mode, e.g. to select the target platform up front
+    /// instead of waiting for a ROM's own `00FE`/`00FF`.
+    pub fn set_high_res(&mut self, high_res: bool) {
+        self.display.set_resolution(if high_res { display::Resolution::High } else { display::Resolution::Low });
+    }
+
     pub fn run(&mut self) {
         self.time.clone().start(TICK_DELAY);
-        
+
+        let mut steps_since_snapshot = 0u64;
         loop {
             match self.step() {
-                Ok(_) => /*thread::sleep(EXEC_DELAY)*/(),
+                Ok(_) => {
+                    steps_since_snapshot += 1;
+                    if steps_since_snapshot >= REWIND_INTERVAL {
+                        steps_since_snapshot = 0;
+                        self.push_rewind_snapshot();
+                    }
+                    /*thread::sleep(EXEC_DELAY)*/
+                },
                 Err(_) => break,
             };
         }
     }
 
+    /// Pushes a fresh snapshot onto the rewind ring buffer, evicting the
+    /// oldest one once [`REWIND_CAPACITY`] is reached.
+    fn push_rewind_snapshot(&mut self) {
+        if self.rewind_buffer.len() == REWIND_CAPACITY {
+            self.rewind_buffer.pop_front();
+        }
+        self.rewind_buffer.push_back(self.snapshot());
+    }
+
+    /// Captures the full architectural state — RAM, registers, `I`, `PC`,
+    /// the stack, the timers, the HP48 flag registers, the XO-CHIP audio
+    /// pattern/pitch, and the display's framebuffer/resolution — for
+    /// serialization or rewinding. See [`MachineState`] for the portable
+    /// byte layout and what's excluded.
+    pub fn snapshot(&self) -> MachineState {
+        let mut ram = [0u8; 0x1000];
+        ram.copy_from_slice(&self.ram[..]);
+
+        MachineState {
+            ram,
+            reg: self.reg,
+            i: self.i,
+            pc: self.pc,
+            sp: self.sp,
+            stack: self.stack,
+            dtime: *self.time.dtime.lock().unwrap(),
+            stime: *self.time.stime.lock().unwrap(),
+            display_buffer: self.display.buffer(),
+            resolution: self.display.resolution(),
+            flags: self.flags,
+            pattern: self.pattern,
+            pitch: self.pitch,
+        }
+    }
+
+    /// Restores a state captured by [`Chip8Emu::snapshot`], dropping any
+    /// predecoded cache entries (they may no longer match RAM), repainting
+    /// the whole screen from the restored framebuffer over
+    /// [`Chip8Emu::get_screen_changes`], and resending the restored audio
+    /// pattern/pitch over [`Chip8Emu::get_audio_changes`] so the front-end
+    /// catches up on both.
+    pub fn restore(&mut self, state: &MachineState) {
+        self.ram[..].copy_from_slice(&state.ram);
+        self.reg = state.reg;
+        self.i = state.i;
+        self.pc = state.pc;
+        self.sp = state.sp;
+        self.stack = state.stack;
+        *self.time.dtime.lock().unwrap() = state.dtime;
+        *self.time.stime.lock().unwrap() = state.stime;
+        self.flags = state.flags;
+        self.pattern = state.pattern;
+        self.pitch = state.pitch;
+
+        if let Some(cache) = &mut self.instruction_cache {
+            *cache = cache::InstructionCache::new(0x1000);
+        }
+
+        self.display.restore(state.display_buffer, state.resolution);
+        self.time.send_audio(audio::AudioCmd::Pitch(self.pitch));
+        self.time.send_audio(audio::AudioCmd::Pattern(self.pattern));
+    }
+
+    /// Rewinds up to `steps` automatic snapshots (each [`REWIND_INTERVAL`]
+    /// instructions apart) and restores the oldest one popped, or does
+    /// nothing if the ring buffer is empty.
+    pub fn rewind(&mut self, steps: usize) {
+        let mut target = None;
+        for _ in 0..steps {
+            match self.rewind_buffer.pop_back() {
+                Some(state) => target = Some(state),
+                None => break,
+            }
+        }
+
+        if let Some(state) = target {
+            self.restore(&state);
+        }
+    }
+
+    /// Runs under an interactive [`debugger::Debugger`] instead of free-running,
+    /// so execution can be paused, stepped, and inspected from a terminal
+    /// prompt while the display/keyboard channels keep driving the front-end.
+    pub fn run_debug(&mut self) {
+        debugger::Debugger::new().attach(self);
+    }
+
     pub fn step(&mut self) -> Result<(),()> {
         #[cfg(debug_assertions)]
         writeln!(self.log, "{:?}", self.reg).unwrap();
-        let instruction = self.fetch();
+        let pc = self.pc;
+        let raw = self.fetch();
         #[cfg(debug_assertions)]
-        writeln!(self.log, "{:#04x?}", instruction).unwrap();
-        let instruction = Self::decode(&instruction);
+        writeln!(self.log, "{:#04x?}", raw).unwrap();
+        let instruction = self.decode_cached(pc, raw);
         #[cfg(debug_assertions)]
         writeln!(self.log, "{:?}", instruction).unwrap();
         self.execute(&instruction)
     }
 
+    /// Returns the decoded instruction fetched from `pc` (raw word `raw`),
+    /// using the predecoded cache when enabled: a hit skips [`Self::decode`]
+    /// entirely, a miss decodes once and populates the entry for next time.
+    fn decode_cached(&mut self, pc: u16, raw: u16) -> Instruction {
+        match &mut self.instruction_cache {
+            Some(cache) => {
+                if cache.get(pc).is_none() {
+                    cache.insert(pc, Self::decode(&raw));
+                }
+                cache.get(pc).unwrap().clone()
+            },
+            None => Self::decode(&raw),
+        }
+    }
+
+    /// Drops any cached instruction decoded from `addr`, called after every
+    /// RAM write a CHIP-8 program can perform so self-modifying code is
+    /// re-decoded on its next fetch instead of running stale.
+    fn invalidate_cache(&mut self, addr: u16) {
+        if let Some(cache) = &mut self.instruction_cache {
+            cache.invalidate(addr);
+        }
+    }
+
     /// Fetch the next instruction from memory
     fn fetch(&mut self) -> u16 {
         let inst1 = (self.ram[self.pc] as u16) << 8; // fetch the first part of the instructions
@@ -238,6 +495,18 @@ impl Chip8Emu {
             inst & 0xF,
         ];
         match nibs {
+            // SUPER-CHIP: scroll the display down by N pixel rows
+            [0x0, 0x0, 0xC, _] => Instruction::ScrollDown((inst & 0xF) as u8),
+            // SUPER-CHIP: scroll the display 4 pixels right
+            [0x0, 0x0, 0xF, 0xB] => Instruction::ScrollRight,
+            // SUPER-CHIP: scroll the display 4 pixels left
+            [0x0, 0x0, 0xF, 0xC] => Instruction::ScrollLeft,
+            // SUPER-CHIP: exit the interpreter
+            [0x0, 0x0, 0xF, 0xD] => Instruction::Exit,
+            // SUPER-CHIP: switch to low-res mode
+            [0x0, 0x0, 0xF, 0xE] => Instruction::LowRes,
+            // SUPER-CHIP: switch to hi-res mode
+            [0x0, 0x0, 0xF, 0xF] => Instruction::HighRes,
             // Clear the display
             [0x0, 0x0, 0xE, 0x0] => Instruction::DispClr,
             // Return from subroutine
@@ -273,7 +542,7 @@ impl Chip8Emu {
             // Bitwise shift right
             [0x8, _, _, 0x6] => Instruction::BitShiftR((inst >> 8 & 0xF) as u8, (inst >> 4 & 0xF) as u8),
             // Store Vy-Vx in Vx
-            [0x8, _, _, 0xE7] => Instruction::InvertSub((inst >> 8 & 0xF) as u8, (inst >> 4 & 0xF) as u8),
+            [0x8, _, _, 0x7] => Instruction::InvertSub((inst >> 8 & 0xF) as u8, (inst >> 4 & 0xF) as u8),
             // Bitwise shift left
             [0x8, _, _, 0xE] => Instruction::BitShiftL((inst >> 8 & 0xF) as u8, (inst >> 4 & 0xF) as u8),
             // Skips if register is not equal to register
@@ -308,6 +577,16 @@ impl Chip8Emu {
             [0xF, _, 0x5, 0x5] => Instruction::RegDmp((inst >> 8 & 0xF) as u8),
             // load register V0 to Vx from memory at memory pointer (I)
             [0xF, _, 0x6, 0x5] => Instruction::RegLoad((inst >> 8 & 0xF) as u8),
+            // SUPER-CHIP: point I at the large hex digit glyph for Vx
+            [0xF, _, 0x3, 0x0] => Instruction::PointLargeChar((inst >> 8 & 0xF) as u8),
+            // SUPER-CHIP: save V0..Vx to the HP48 flag registers
+            [0xF, _, 0x7, 0x5] => Instruction::FlagSave((inst >> 8 & 0xF) as u8),
+            // SUPER-CHIP: load V0..Vx from the HP48 flag registers
+            [0xF, _, 0x8, 0x5] => Instruction::FlagLoad((inst >> 8 & 0xF) as u8),
+            // XO-CHIP: set the pitch register from Vx
+            [0xF, _, 0x3, 0xA] => Instruction::PitchSet((inst >> 8 & 0xF) as u8),
+            // XO-CHIP: load the 16-byte audio pattern buffer from memory at I
+            [0xF, 0x0, 0x0, 0x2] => Instruction::AudioLoad,
             // No operation
             _ => Instruction::StopExecution
         }
@@ -387,16 +666,25 @@ impl Chip8Emu {
             // Bitwise OR
             Instruction::BitOr(x, y) => {
                 self.reg[*x as usize] |= self.reg[*y as usize];
+                if self.quirks.logic {
+                    self.reg[0xF] = 0;
+                }
                 Ok(())
             },
             // Bitwise AND
             Instruction::BitAnd(x, y) => {
                 self.reg[*x as usize] &= self.reg[*y as usize];
+                if self.quirks.logic {
+                    self.reg[0xF] = 0;
+                }
                 Ok(())
             },
             // Bitwise XOR
             Instruction::BitXor(x, y) => {
                 self.reg[*x as usize] ^= self.reg[*y as usize];
+                if self.quirks.logic {
+                    self.reg[0xF] = 0;
+                }
                 Ok(())
             },
             // Addition
@@ -414,27 +702,33 @@ impl Chip8Emu {
                 } else {
                     self.reg[0xF] = 0
                 }
-                self.reg[*x as usize] -= self.reg[*y as usize];
+                self.reg[*x as usize] = self.reg[*x as usize].wrapping_sub(self.reg[*y as usize]);
                 Ok(())
             },
             // Bitwise Shift right, store least significant bit of initial value in VF
-            Instruction::BitShiftR(x, _y) => {
+            Instruction::BitShiftR(x, y) => {
+                if !self.quirks.shift {
+                    self.reg[*x as usize] = self.reg[*y as usize];
+                }
                 self.reg[0xF] = self.reg[*x as usize] & 0b1;
                 self.reg[*x as usize] >>= 1;
                 Ok(())
             },
             // Store Vy-Vx in Vx
             Instruction::InvertSub(x, y) => {
-                if self.reg[*x as usize] > self.reg[*y as usize] {
+                if self.reg[*y as usize] >= self.reg[*x as usize] {
                     self.reg[0xF] = 1
                 } else {
                     self.reg[0xF] = 0
                 }
-                self.reg[*x as usize] = y - self.reg[*x as usize];
+                self.reg[*x as usize] = self.reg[*y as usize].wrapping_sub(self.reg[*x as usize]);
                 Ok(())
             },
             // Bitwise Shift left, store most significant bit of initial value in VF
-            Instruction::BitShiftL(x, _y) => {
+            Instruction::BitShiftL(x, y) => {
+                if !self.quirks.shift {
+                    self.reg[*x as usize] = self.reg[*y as usize];
+                }
                 self.reg[0xF] = self.reg[*x as usize] >> 7;
                 self.reg[*x as usize] <<= 1;
                 Ok(())
@@ -453,7 +747,11 @@ impl Chip8Emu {
             },
             // Jump to address + V0
             Instruction::FlowJmpV0(nnn) => {
-                self.pc = *nnn + self.reg[0] as u16;
+                self.pc = if self.quirks.jump {
+                    *nnn + self.reg[(*nnn >> 8 & 0xF) as usize] as u16
+                } else {
+                    *nnn + self.reg[0] as u16
+                };
                 Ok(())
             },
             // Random generation
@@ -465,13 +763,16 @@ impl Chip8Emu {
             Instruction::DispDraw(x, y, n) => {
                 let x = self.reg[*x as usize];
                 let y = self.reg[*y as usize];
-                let n = *n as u16;
+                let wide = *n == 0 && self.display.resolution() == display::Resolution::High;
+                let n = if wide { 32 } else { *n as u16 };
                 let data = &self.ram[self.i..=self.i+n-1];
 
                 let sprite = display::Sprite {
                     x,
                     y,
                     data,
+                    wide,
+                    clip: self.quirks.clip,
                 };
 
                 match self.display.draw(sprite) {
@@ -482,6 +783,66 @@ impl Chip8Emu {
                     Err(()) => Err(())
                 }
             },
+            // SUPER-CHIP: scroll the display down by N pixel rows
+            Instruction::ScrollDown(n) => {
+                self.display.scroll_down(*n);
+                Ok(())
+            },
+            // SUPER-CHIP: scroll the display 4 pixels right
+            Instruction::ScrollRight => {
+                self.display.scroll_right();
+                Ok(())
+            },
+            // SUPER-CHIP: scroll the display 4 pixels left
+            Instruction::ScrollLeft => {
+                self.display.scroll_left();
+                Ok(())
+            },
+            // SUPER-CHIP: exit the interpreter
+            Instruction::Exit => Err(()),
+            // SUPER-CHIP: switch to low-res mode
+            Instruction::LowRes => {
+                self.display.set_resolution(display::Resolution::Low);
+                Ok(())
+            },
+            // SUPER-CHIP: switch to hi-res mode
+            Instruction::HighRes => {
+                self.display.set_resolution(display::Resolution::High);
+                Ok(())
+            },
+            // SUPER-CHIP: point I at the large hex digit glyph for Vx
+            Instruction::PointLargeChar(x) => {
+                self.i = LARGE_FONTPACK_LOCATION as u16 + self.reg[*x as usize] as u16 * 10;
+                Ok(())
+            },
+            // SUPER-CHIP: save V0..Vx to the HP48 flag registers
+            Instruction::FlagSave(x) => {
+                for reg_id in 0x0..=(*x as usize).min(self.flags.len() - 1) {
+                    self.flags[reg_id] = self.reg[reg_id];
+                }
+                Ok(())
+            },
+            // SUPER-CHIP: load V0..Vx from the HP48 flag registers
+            Instruction::FlagLoad(x) => {
+                for reg_id in 0x0..=(*x as usize).min(self.flags.len() - 1) {
+                    self.reg[reg_id] = self.flags[reg_id];
+                }
+                Ok(())
+            },
+            // XO-CHIP: set the pitch register from Vx
+            Instruction::PitchSet(x) => {
+                self.pitch = self.reg[*x as usize];
+                self.time.send_audio(audio::AudioCmd::Pitch(self.pitch));
+                Ok(())
+            },
+            // XO-CHIP: load the 16-byte audio pattern buffer from memory at I
+            Instruction::AudioLoad => {
+                for byte_id in 0x0..16u16 {
+                    self.pattern[byte_id as usize] = self.ram[self.i + byte_id];
+                }
+                self.time.send_audio(audio::AudioCmd::Pattern(self.pattern));
+                Ok(())
+            },
             // Skip if key is pressed
             Instruction::CondKey(x) => {
                 if self.keyboard.is_key_pressed(self.reg[*x as usize]) {
@@ -546,7 +907,9 @@ impl Chip8Emu {
                 const BASE: u8 = 10;
 
                 for i in 0u16..=2 {
-                    self.ram[self.i+2-i] = x % BASE;
+                    let addr = self.i+2-i;
+                    self.ram[addr] = x % BASE;
+                    self.invalidate_cache(addr);
                     x /= BASE;
                 }
 
@@ -555,7 +918,12 @@ impl Chip8Emu {
             // Saves register to memory
             Instruction::RegDmp(x) => {
                 for reg_id in 0x0..=(*x as u16) {
-                    self.ram[self.i+reg_id] = self.reg[reg_id as usize];
+                    let addr = self.i+reg_id;
+                    self.ram[addr] = self.reg[reg_id as usize];
+                    self.invalidate_cache(addr);
+                }
+                if !self.quirks.load_store {
+                    self.i += *x as u16 + 1;
                 }
 
                 Ok(())
@@ -565,6 +933,9 @@ impl Chip8Emu {
                 for reg_id in 0x0..=(*x as u16) {
                     self.reg[reg_id as usize] = self.ram[self.i+reg_id];
                 }
+                if !self.quirks.load_store {
+                    self.i += *x as u16 + 1;
+                }
 
                 Ok(())
             },
@@ -579,6 +950,13 @@ impl Chip8Emu {
         self.time.beep.clone()
     }
 
+    /// Subscribes to XO-CHIP audio commands (pattern/pitch/playing changes),
+    /// for front-ends that can render actual waveforms instead of just
+    /// [`Chip8Emu::is_beeping`]'s on/off flag.
+    pub fn get_audio_changes(&mut self) -> Receiver<audio::AudioCmd> {
+        self.time.get_audio_changes_pipe()
+    }
+
     pub fn get_screen_changes(&mut self) -> Receiver<Change> {
         self.display.get_changes_pipe()
     }
@@ -0,0 +1,17 @@
+/// XO-CHIP's audio subsystem: front-ends receive a stream of these,
+/// mirroring how [`super::display::DisplayCmd`] streams pixel changes,
+/// instead of a single on/off beep flag.
+#[derive(Debug, Clone)]
+pub enum AudioCmd {
+    /// The 128-bit waveform pattern to play, one bit per sample, MSB first (`F002`).
+    Pattern([u8; 16]),
+    /// The pitch register, controlling playback rate (`Fx3A`). See [`playback_rate`].
+    Pitch(u8),
+    /// Whether the pattern should currently be playing (the sound timer is non-zero).
+    Playing(bool),
+}
+
+/// The XO-CHIP sample playback rate, in Hz, for a given pitch register value.
+pub fn playback_rate(pitch: u8) -> f64 {
+    4000.0 * 2f64.powf((pitch as f64 - 64.0) / 48.0)
+}
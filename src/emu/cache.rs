@@ -0,0 +1,41 @@
+use super::Instruction;
+
+/// A predecoded-instruction cache indexed by byte address, so the hot
+/// fetch/execute loop can skip [`super::Chip8Emu::decode`] on a hit. Entries
+/// are populated lazily on first fetch and dropped by
+/// [`InstructionCache::invalidate`] whenever a RAM write can have touched
+/// code, so self-modifying ROMs stay correct at the cost of one re-decode.
+pub struct InstructionCache {
+    entries: Vec<Option<Instruction>>,
+}
+
+impl InstructionCache {
+    pub fn new(size: usize) -> Self {
+        Self {
+            entries: (0..size).map(|_| None).collect(),
+        }
+    }
+
+    /// The cached instruction at `addr`, if one has been decoded there since
+    /// the last invalidation.
+    pub fn get(&self, addr: u16) -> Option<&Instruction> {
+        self.entries.get(addr as usize).and_then(|entry| entry.as_ref())
+    }
+
+    /// Populates (or overwrites) the entry at `addr`.
+    pub fn insert(&mut self, addr: u16, instruction: Instruction) {
+        if let Some(entry) = self.entries.get_mut(addr as usize) {
+            *entry = Some(instruction);
+        }
+    }
+
+    /// Drops any cached instruction whose 2-byte word overlaps `addr`, i.e.
+    /// one fetched starting at `addr` or at `addr - 1`.
+    pub fn invalidate(&mut self, addr: u16) {
+        for touched in [addr, addr.wrapping_sub(1)] {
+            if let Some(entry) = self.entries.get_mut(touched as usize) {
+                *entry = None;
+            }
+        }
+    }
+}
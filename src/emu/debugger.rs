@@ -0,0 +1,263 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, BufRead, Write},
+};
+
+use super::{Chip8Emu, Instruction};
+
+/// A command-driven monitor wrapped around a [`Chip8Emu`], offering
+/// breakpoints, memory watchpoints, and single-stepping without touching the
+/// emulator's own `run`/`step` loop. The emulator's display/keyboard mpsc
+/// channels keep driving the front-end exactly as they do under `run`; this
+/// only gates when `step()` gets called.
+pub struct Debugger {
+    breakpoints: HashSet<u16>,
+    /// Watched address -> the value it held as of the last step.
+    watchpoints: HashMap<u16, u8>,
+    /// Print the disassembly of every instruction as it steps.
+    trace: bool,
+}
+
+/// Outcome of one [`Debugger::single_step`] call, distinguishing a watched
+/// address changing from an ordinary step so `c`/`s` know when to stop.
+enum StepOutcome {
+    /// Stepped normally; no watched address changed.
+    Continued,
+    /// Stepped normally, but a watched address changed.
+    Watchpoint,
+    /// The emulator halted (e.g. hit [`Instruction::StopExecution`]).
+    Halted,
+}
+
+impl Debugger {
+    pub fn new() -> Self {
+        Self {
+            breakpoints: HashSet::new(),
+            watchpoints: HashMap::new(),
+            trace: false,
+        }
+    }
+
+    /// Runs an interactive command loop against `emu`, reading commands from
+    /// stdin until the session is quit or the emulator halts.
+    pub fn attach(&mut self, emu: &mut Chip8Emu) {
+        emu.time.clone().start(super::TICK_DELAY);
+
+        let stdin = io::stdin();
+        self.prompt();
+
+        for line in stdin.lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+
+            if !self.command(emu, line.trim()) {
+                break;
+            }
+
+            self.prompt();
+        }
+    }
+
+    fn prompt(&self) {
+        print!("(chip8dbg) ");
+        io::stdout().flush().unwrap();
+    }
+
+    /// Runs a single command line, returning `false` once the session (or
+    /// the emulator) should stop.
+    fn command(&mut self, emu: &mut Chip8Emu, line: &str) -> bool {
+        let mut parts = line.split_whitespace();
+        let verb = match parts.next() {
+            Some(verb) => verb,
+            None => return true,
+        };
+
+        match verb {
+            "b" => match parts.next().and_then(parse_addr) {
+                Some(addr) => {
+                    self.breakpoints.insert(addr);
+                    println!("breakpoint set at {:#06x}", addr);
+                },
+                None => println!("usage: b <addr>"),
+            },
+            "clear" => match parts.next().and_then(parse_addr) {
+                Some(addr) => {
+                    self.breakpoints.remove(&addr);
+                    println!("breakpoint cleared at {:#06x}", addr);
+                },
+                None => println!("usage: clear <addr>"),
+            },
+            "w" => match parts.next().and_then(parse_addr) {
+                Some(addr) => {
+                    self.watchpoints.insert(addr, emu.ram[addr]);
+                    println!("watchpoint set at {:#06x} (current value {:#04x})", addr, emu.ram[addr]);
+                },
+                None => println!("usage: w <addr>"),
+            },
+            "s" => {
+                let count: u32 = parts.next().and_then(|s| s.parse().ok()).unwrap_or(1);
+                for _ in 0..count {
+                    match self.single_step(emu) {
+                        StepOutcome::Halted => return false,
+                        StepOutcome::Watchpoint => break,
+                        StepOutcome::Continued => (),
+                    }
+                }
+            },
+            "c" => loop {
+                match self.single_step(emu) {
+                    StepOutcome::Halted => return false,
+                    StepOutcome::Watchpoint => break,
+                    StepOutcome::Continued => (),
+                }
+                if self.breakpoints.contains(&emu.pc) {
+                    println!("hit breakpoint at {:#06x}", emu.pc);
+                    break;
+                }
+            },
+            "reg" => self.dump_registers(emu),
+            "mem" => {
+                let addr = parts.next().and_then(parse_addr).unwrap_or(emu.pc);
+                let len: u16 = parts.next().and_then(|s| s.parse().ok()).unwrap_or(16);
+                self.dump_memory(emu, addr, len);
+            },
+            "dis" => {
+                let addr = parts.next().and_then(parse_addr).unwrap_or(emu.pc);
+                let len: u16 = parts.next().and_then(|s| s.parse().ok()).unwrap_or(1);
+                self.dump_disassembly(emu, addr, len);
+            },
+            "t" => {
+                self.trace = !self.trace;
+                println!("trace {}", if self.trace { "on" } else { "off" });
+            },
+            "q" => return false,
+            _ => println!("unknown command: {}", verb),
+        }
+
+        true
+    }
+
+    /// Fetches, decodes, optionally traces, diffs watchpoints around, and
+    /// executes one instruction.
+    fn single_step(&mut self, emu: &mut Chip8Emu) -> StepOutcome {
+        let pc = emu.pc;
+        let raw = (emu.ram[pc] as u16) << 8 | emu.ram[pc + 1] as u16;
+        let instruction = Chip8Emu::decode(&raw);
+
+        if self.trace {
+            println!("{:#06x}: {}", pc, disassemble(&instruction));
+        }
+
+        let before: Vec<(u16, u8)> = self.watchpoints.keys().map(|&addr| (addr, emu.ram[addr])).collect();
+
+        if emu.step().is_err() {
+            println!("execution stopped at {:#06x}", pc);
+            return StepOutcome::Halted;
+        }
+
+        let mut hit = false;
+        for (addr, old) in before {
+            let new = emu.ram[addr];
+            if new != old {
+                println!("watchpoint {:#06x} changed {:#04x} -> {:#04x}", addr, old, new);
+                self.watchpoints.insert(addr, new);
+                hit = true;
+            }
+        }
+
+        if hit { StepOutcome::Watchpoint } else { StepOutcome::Continued }
+    }
+
+    fn dump_registers(&self, emu: &Chip8Emu) {
+        for (index, value) in emu.reg.iter().enumerate() {
+            print!("V{:X}={:#04x} ", index, value);
+        }
+        println!();
+        println!("I={:#06x} PC={:#06x} SP={:#04x}", emu.i, emu.pc, emu.sp);
+        println!("stack: {:04x?}", &emu.stack[..emu.sp as usize]);
+    }
+
+    fn dump_memory(&self, emu: &Chip8Emu, addr: u16, len: u16) {
+        for offset in 0..len {
+            if offset % 8 == 0 {
+                print!("\n{:#06x}: ", addr.saturating_add(offset));
+            }
+            print!("{:02x} ", emu.ram[addr.saturating_add(offset)]);
+        }
+        println!();
+    }
+
+    fn dump_disassembly(&self, emu: &Chip8Emu, addr: u16, len: u16) {
+        let mut addr = addr;
+        for _ in 0..len {
+            let raw = (emu.ram[addr] as u16) << 8 | emu.ram[addr + 1] as u16;
+            println!("{:#06x}: {}", addr, disassemble(&Chip8Emu::decode(&raw)));
+            addr = addr.saturating_add(2);
+        }
+    }
+}
+
+/// Formats a decoded instruction as a one-line disassembly, e.g.
+/// `Instruction::FlowCall(0x2nn)` becomes `CALL 0x2nn`.
+fn disassemble(inst: &Instruction) -> String {
+    match inst {
+        Instruction::Call(nnn) => format!("SYS {:#05x}", nnn),
+        Instruction::DispClr => "CLS".to_string(),
+        Instruction::FlowRet => "RET".to_string(),
+        Instruction::FlowJmp(nnn) => format!("JP {:#05x}", nnn),
+        Instruction::FlowCall(nnn) => format!("CALL {:#05x}", nnn),
+        Instruction::CondEqL(x, nn) => format!("SE V{:X}, {:#04x}", x, nn),
+        Instruction::CondNoEqL(x, nn) => format!("SNE V{:X}, {:#04x}", x, nn),
+        Instruction::CondEqRg(x, y) => format!("SE V{:X}, V{:X}", x, y),
+        Instruction::RegConst(x, nn) => format!("LD V{:X}, {:#04x}", x, nn),
+        Instruction::RegAdd(x, nn) => format!("ADD V{:X}, {:#04x}", x, nn),
+        Instruction::Assign(x, y) => format!("LD V{:X}, V{:X}", x, y),
+        Instruction::BitOr(x, y) => format!("OR V{:X}, V{:X}", x, y),
+        Instruction::BitAnd(x, y) => format!("AND V{:X}, V{:X}", x, y),
+        Instruction::BitXor(x, y) => format!("XOR V{:X}, V{:X}", x, y),
+        Instruction::MathAdd(x, y) => format!("ADD V{:X}, V{:X}", x, y),
+        Instruction::MathSub(x, y) => format!("SUB V{:X}, V{:X}", x, y),
+        Instruction::BitShiftR(x, y) => format!("SHR V{:X}, V{:X}", x, y),
+        Instruction::InvertSub(x, y) => format!("SUBN V{:X}, V{:X}", x, y),
+        Instruction::BitShiftL(x, y) => format!("SHL V{:X}, V{:X}", x, y),
+        Instruction::CondNoEqRg(x, y) => format!("SNE V{:X}, V{:X}", x, y),
+        Instruction::SetPoint(nnn) => format!("LD I, {:#05x}", nnn),
+        Instruction::FlowJmpV0(nnn) => format!("JP V0, {:#05x}", nnn),
+        Instruction::RNG(x, nn) => format!("RND V{:X}, {:#04x}", x, nn),
+        Instruction::DispDraw(x, y, n) => format!("DRW V{:X}, V{:X}, {:#03x}", x, y, n),
+        Instruction::CondKey(x) => format!("SKP V{:X}", x),
+        Instruction::CondNotKey(x) => format!("SKNP V{:X}", x),
+        Instruction::DelTimrGet(x) => format!("LD V{:X}, DT", x),
+        Instruction::WaitKey(x) => format!("LD V{:X}, K", x),
+        Instruction::DelTimrSet(x) => format!("LD DT, V{:X}", x),
+        Instruction::SndTimrSet(x) => format!("LD ST, V{:X}", x),
+        Instruction::PointAdd(x) => format!("ADD I, V{:X}", x),
+        Instruction::PointChar(x) => format!("LD F, V{:X}", x),
+        Instruction::BCDStore(x) => format!("LD B, V{:X}", x),
+        Instruction::RegDmp(x) => format!("LD [I], V{:X}", x),
+        Instruction::RegLoad(x) => format!("LD V{:X}, [I]", x),
+        Instruction::StopExecution => "???".to_string(),
+        Instruction::ScrollDown(n) => format!("SCD {:#03x}", n),
+        Instruction::ScrollRight => "SCR".to_string(),
+        Instruction::ScrollLeft => "SCL".to_string(),
+        Instruction::Exit => "EXIT".to_string(),
+        Instruction::LowRes => "LOW".to_string(),
+        Instruction::HighRes => "HIGH".to_string(),
+        Instruction::PointLargeChar(x) => format!("LD HF, V{:X}", x),
+        Instruction::FlagSave(x) => format!("LD R, V{:X}", x),
+        Instruction::FlagLoad(x) => format!("LD V{:X}, R", x),
+        Instruction::PitchSet(x) => format!("PITCH V{:X}", x),
+        Instruction::AudioLoad => "LD PATTERN, [I]".to_string(),
+    }
+}
+
+/// Parses a command argument as either a `0x`-prefixed hex address or a
+/// plain decimal one.
+fn parse_addr(s: &str) -> Option<u16> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u16::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
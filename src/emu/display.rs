@@ -14,6 +14,12 @@ pub struct Sprite<'a> {
 	pub data: &'a [u8],
 	pub x: u8,
 	pub y: u8,
+	/// `true` for a SUPER-CHIP 16x16 sprite (`Dxy0` in hi-res mode, two
+	/// bytes per row), `false` for the classic 8-pixel-wide sprite.
+	pub wide: bool,
+	/// `true` clips a sprite at the screen edge (SUPER-CHIP); `false` wraps
+	/// it around to the opposite edge (COSMAC VIP), per [`crate::emu::Quirks`].
+	pub clip: bool,
 }
 
 /// An enum that contains all the possible commands for the view to process
@@ -27,12 +33,39 @@ pub enum DisplayCmd {
 	Clear,
 }
 
+/// The two resolutions SUPER-CHIP programs can switch between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+	/// 64x32, the original CHIP-8 resolution.
+	Low,
+	/// 128x64, SUPER-CHIP's hi-res mode.
+	High,
+}
+
+impl Resolution {
+	fn width(self) -> u16 {
+		match self {
+			Resolution::Low => 64,
+			Resolution::High => 128,
+		}
+	}
+
+	fn height(self) -> u16 {
+		match self {
+			Resolution::Low => 32,
+			Resolution::High => 64,
+		}
+	}
+}
+
 /// A representation of the screen of the emulator and its changes.
 pub struct Display {
-	/// This buffer contains the image to be displayed. It stores the screens state in binary form.
-	/// The screen is 64 pixels wide and 32 pixels high so the screen data gets stored as 32 64 bit rows with each
-	/// bit representing 1 pixel. The bits themselfs just represent two different colors at a given location
-	buffer: [u64; 32],
+	/// This buffer contains the image to be displayed, one row per `u128`
+	/// with the leftmost pixel of a row in the most significant bit. Sized
+	/// for the largest supported resolution (128x64); in [`Resolution::Low`]
+	/// only the first 32 rows and low 64 bits of each row are addressable.
+	buffer: [u128; 64],
+	resolution: Resolution,
 	changes: Vec<Sender<DisplayCmd>>,
 	#[cfg(debug_assertions)]
 	logfile: File,
@@ -42,15 +75,40 @@ impl Display {
 	/// constructs a new display
 	pub fn new() -> Self {
 		Self {
-			buffer: [0x0; 32],
+			buffer: [0x0; 64],
+			resolution: Resolution::Low,
 			changes: Vec::new(),
 			#[cfg(debug_assertions)]
 			logfile: File::create("display.log").unwrap(),
 		}
 	}
 
+	pub fn resolution(&self) -> Resolution {
+		self.resolution
+	}
+
+	/// The raw framebuffer, for capturing into a save-state snapshot.
+	pub(super) fn buffer(&self) -> [u128; 64] {
+		self.buffer
+	}
+
+	/// Restores a previously captured framebuffer and resolution, then
+	/// repaints the whole screen so the front-end catches up.
+	pub(super) fn restore(&mut self, buffer: [u128; 64], resolution: Resolution) {
+		self.buffer = buffer;
+		self.resolution = resolution;
+		self.resend_full_frame();
+	}
+
+	/// Switches resolution (`00FE`/`00FF`) and clears the screen, matching
+	/// how SUPER-CHIP interpreters handle a mode switch.
+	pub fn set_resolution(&mut self, resolution: Resolution) {
+		self.resolution = resolution;
+		self.clear();
+	}
+
 	pub fn clear(&mut self) {
-		self.buffer = [0x0; 32];
+		self.buffer = [0x0; 64];
 		for tx in self.changes.iter() {
 			match tx.send(DisplayCmd::Clear) {
 				Ok(_) => (),
@@ -59,42 +117,146 @@ impl Display {
 		}
 	}
 
+	/// Scrolls the whole screen down by `n` rows (`00Cn`), shifting in blank
+	/// rows at the top.
+	pub fn scroll_down(&mut self, n: u8) {
+		let height = self.resolution.height() as usize;
+		let n = n as usize;
+
+		for row in (0..height).rev() {
+			self.buffer[row] = if row >= n { self.buffer[row - n] } else { 0 };
+		}
+
+		self.resend_full_frame();
+	}
+
+	/// Scrolls the whole screen 4 pixels right (`00FB`).
+	pub fn scroll_right(&mut self) {
+		let height = self.resolution.height() as usize;
+		for row in self.buffer.iter_mut().take(height) {
+			*row >>= 4;
+		}
+		self.resend_full_frame();
+	}
+
+	/// Scrolls the whole screen 4 pixels left (`00FC`).
+	pub fn scroll_left(&mut self) {
+		let mask = self.width_mask();
+		let height = self.resolution.height() as usize;
+		for row in self.buffer.iter_mut().take(height) {
+			*row = (*row << 4) & mask;
+		}
+		self.resend_full_frame();
+	}
+
+	fn width_mask(&self) -> u128 {
+		match self.resolution {
+			Resolution::Low => (1u128 << 64) - 1,
+			Resolution::High => u128::MAX,
+		}
+	}
+
+	/// Re-announces every row as a `Change` after an operation (scrolling)
+	/// that can touch the entire screen at once. `Change(data, x, y)` means
+	/// one 8-pixel byte per row starting at `y`, all at column `x`, so each
+	/// horizontal byte of a row is sent as its own `Change` at that byte's
+	/// column instead of being packed into a single multi-byte message.
+	fn resend_full_frame(&mut self) {
+		let width = self.resolution.width();
+		let height = self.resolution.height();
+		let row_bytes = (width / 8) as usize;
+
+		for y in 0..height {
+			let row = self.buffer[y as usize];
+			for byte_index in 0..row_bytes {
+				let shift = width as u32 - 8 * (byte_index as u32 + 1);
+				let byte = (row >> shift) as u8;
+
+				for tx in self.changes.iter() {
+					let _ = tx.send(DisplayCmd::Change(vec![byte], (byte_index * 8) as u8, y as u8));
+				}
+			}
+		}
+	}
+
 	pub fn draw(&mut self, sprite: Sprite) -> Result<bool,()> {
 		let mut updated = false;
-		let Sprite { data, x, y} = sprite;
+		let mut wrapped = false;
+		let Sprite { data, x, y, wide, clip } = sprite;
 		let mut changes = Vec::new();
 
 		#[cfg(debug_assertions)]
 		writeln!(self.logfile, "Sprite: {:?}", sprite).unwrap();
 
-		let left = x <= 56;
-		#[cfg(debug_assertions)]
-		writeln!(self.logfile, "left {:?}", left).unwrap();
+		let width = self.resolution.width() as i32;
+		let height = self.resolution.height() as i32;
+		// The sprite origin wraps around the screen (a draw at e.g. x = 70 on
+		// a 64-wide screen starts at column 6), regardless of whether the
+		// overrun is then clipped or wrapped per the clipping quirk.
+		let x = x as i32 % width;
+		let y = y as i32 % height;
+		let sprite_width: i32 = if wide { 16 } else { 8 };
+		let row_bytes: usize = if wide { 2 } else { 1 };
+		let mask = self.width_mask();
 
-		for (index, row) in data.iter().enumerate() {
-			if y as usize + index >= 32 {
-				break;
+		for (index, row) in data.chunks(row_bytes).enumerate() {
+			let mut row_y = y + index as i32;
+			if row_y >= height {
+				// Past the bottom edge: clipped, or wrapped back around to the
+				// top row per the clipping quirk.
+				if clip {
+					break;
+				}
+				wrapped = true;
+				row_y %= height;
 			}
 
-			let buf_row;
-			if left {
-				buf_row = (*row as u64) << (56 - x); // stretch the current row to full width
-			} else {
-				buf_row = (*row as u64) >> (x - 56); // stretch the current row to full width
-			}
-			updated |= self.buffer[y as usize + index] & buf_row > 0; // check if the buffer gets updated
-			self.buffer[y as usize + index] ^= buf_row; // write changes to buffer
-			if left {
-				changes.push((self.buffer[y as usize + index] >> (56 - x) & 0xFF) as u8); // get result as change
+			let row_bits: u128 = row.iter().fold(0u128, |acc, byte| (acc << 8) | *byte as u128);
+
+			// Position the sprite row's leftmost pixel at column `x`. A
+			// negative shift means part of the sprite runs past the right
+			// edge of the screen: clipped by shifting it back in and
+			// dropping the overflowing bits, or wrapped around to column 0
+			// by rotating within the row instead, per the clipping quirk.
+			let shift = width - x - sprite_width;
+			let buf_row: u128 = if shift >= 0 {
+				row_bits << shift
+			} else if clip {
+				row_bits >> -shift
 			} else {
-				changes.push((self.buffer[y as usize + index] << (x - 56) & 0xFF) as u8); // get result as change
+				wrapped = true;
+				let amount = shift.rem_euclid(width) as u32;
+				((row_bits << amount) | (row_bits >> (width as u32 - amount))) & mask
+			};
+
+			updated |= self.buffer[row_y as usize] & buf_row > 0;
+			self.buffer[row_y as usize] ^= buf_row;
+
+			if !wrapped {
+				let result_bits: u128 = if shift >= 0 {
+					(self.buffer[row_y as usize] >> shift) & ((1u128 << sprite_width) - 1)
+				} else {
+					(self.buffer[row_y as usize] << -shift) & ((1u128 << sprite_width) - 1)
+				};
+
+				for byte_index in (0..row_bytes).rev() {
+					changes.push((result_bits >> (8 * byte_index)) as u8);
+				}
 			}
 		}
 
-		for tx in self.changes.iter() {
-			match tx.send(DisplayCmd::Change(changes.clone(), x, y)) {
-				Ok(_) => (),
-				Err(_) => (),
+		if wrapped {
+			// A sprite that wrapped can touch both edges of the row at
+			// once, which the single-anchor `Change` command can't express;
+			// resyncing the whole frame is simpler than teaching the wire
+			// format a second anchor.
+			self.resend_full_frame();
+		} else {
+			for tx in self.changes.iter() {
+				match tx.send(DisplayCmd::Change(changes.clone(), x as u8, y as u8)) {
+					Ok(_) => (),
+					Err(_) => (),
+				}
 			}
 		}
 
@@ -106,4 +268,4 @@ impl Display {
 		self.changes.push(tx);
 		rx
 	}
-}
\ No newline at end of file
+}
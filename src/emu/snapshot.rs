@@ -0,0 +1,134 @@
+use std::io::{self, Error, ErrorKind, Read, Write};
+
+use super::display::Resolution;
+
+/// Identifies the byte layout below; bumped whenever it changes
+/// incompatibly so an old save state is rejected instead of misread.
+const VERSION: u8 = 3;
+
+const RAM_SIZE: usize = 0x1000;
+const REG_COUNT: usize = 0x10;
+const STACK_SIZE: usize = 0xFF;
+const DISPLAY_ROWS: usize = 64;
+const FLAG_COUNT: usize = 8;
+const PATTERN_SIZE: usize = 16;
+
+/// A full capture of [`super::Chip8Emu`]'s architectural state, portable
+/// across runs via [`MachineState::to_bytes`]/[`MachineState::from_bytes`].
+/// Everything needed to resume execution (and repaint the screen) exactly
+/// where it left off is here except the debug-only `log` file, which isn't
+/// part of the architecture.
+///
+/// Byte layout, version 3 (all multi-byte fields little-endian):
+/// `[version: u8][ram: 4096][reg: 16][i: u16][pc: u16][sp: u8][stack: 255 * u16][dtime: u8][stime: u8][display_buffer: 64 * u128][resolution: u8][flags: 8][pattern: 16][pitch: u8]`
+#[derive(Debug, Clone)]
+pub struct MachineState {
+    pub(super) ram: [u8; RAM_SIZE],
+    pub(super) reg: [u8; REG_COUNT],
+    pub(super) i: u16,
+    pub(super) pc: u16,
+    pub(super) sp: u8,
+    pub(super) stack: [u16; STACK_SIZE],
+    pub(super) dtime: u8,
+    pub(super) stime: u8,
+    pub(super) display_buffer: [u128; DISPLAY_ROWS],
+    pub(super) resolution: Resolution,
+    pub(super) flags: [u8; FLAG_COUNT],
+    pub(super) pattern: [u8; PATTERN_SIZE],
+    pub(super) pitch: u8,
+}
+
+impl MachineState {
+    /// The exact length of the blob produced by [`MachineState::to_bytes`].
+    const ENCODED_LEN: usize = 1
+        + RAM_SIZE + REG_COUNT + 2 + 2 + 1 + STACK_SIZE * 2 + 1 + 1
+        + DISPLAY_ROWS * 16 + 1
+        + FLAG_COUNT + PATTERN_SIZE + 1;
+
+    /// Encodes this state into the versioned blob described on the type.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::ENCODED_LEN);
+        out.push(VERSION);
+        out.extend_from_slice(&self.ram);
+        out.extend_from_slice(&self.reg);
+        out.extend_from_slice(&self.i.to_le_bytes());
+        out.extend_from_slice(&self.pc.to_le_bytes());
+        out.push(self.sp);
+        for word in &self.stack {
+            out.extend_from_slice(&word.to_le_bytes());
+        }
+        out.push(self.dtime);
+        out.push(self.stime);
+        for row in &self.display_buffer {
+            out.extend_from_slice(&row.to_le_bytes());
+        }
+        out.push(match self.resolution {
+            Resolution::Low => 0,
+            Resolution::High => 1,
+        });
+        out.extend_from_slice(&self.flags);
+        out.extend_from_slice(&self.pattern);
+        out.push(self.pitch);
+        out
+    }
+
+    /// Decodes a blob produced by [`MachineState::to_bytes`], rejecting a
+    /// mismatched version or a blob of the wrong length.
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        if bytes.len() != Self::ENCODED_LEN {
+            return Err(Error::new(ErrorKind::InvalidData, "machine state has the wrong length"));
+        }
+        if bytes[0] != VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, format!("unsupported machine state version {}", bytes[0])));
+        }
+
+        let mut offset = 1;
+        let mut take = |len: usize| {
+            let slice = &bytes[offset..offset + len];
+            offset += len;
+            slice
+        };
+
+        let mut ram = [0u8; RAM_SIZE];
+        ram.copy_from_slice(take(RAM_SIZE));
+        let mut reg = [0u8; REG_COUNT];
+        reg.copy_from_slice(take(REG_COUNT));
+        let i = u16::from_le_bytes(take(2).try_into().unwrap());
+        let pc = u16::from_le_bytes(take(2).try_into().unwrap());
+        let sp = take(1)[0];
+        let mut stack = [0u16; STACK_SIZE];
+        for word in stack.iter_mut() {
+            *word = u16::from_le_bytes(take(2).try_into().unwrap());
+        }
+        let dtime = take(1)[0];
+        let stime = take(1)[0];
+        let mut display_buffer = [0u128; DISPLAY_ROWS];
+        for row in display_buffer.iter_mut() {
+            *row = u128::from_le_bytes(take(16).try_into().unwrap());
+        }
+        let resolution = match take(1)[0] {
+            0 => Resolution::Low,
+            1 => Resolution::High,
+            other => return Err(Error::new(ErrorKind::InvalidData, format!("unknown resolution tag {}", other))),
+        };
+        let mut flags = [0u8; FLAG_COUNT];
+        flags.copy_from_slice(take(FLAG_COUNT));
+        let mut pattern = [0u8; PATTERN_SIZE];
+        pattern.copy_from_slice(take(PATTERN_SIZE));
+        let pitch = take(1)[0];
+
+        Ok(Self { ram, reg, i, pc, sp, stack, dtime, stime, display_buffer, resolution, flags, pattern, pitch })
+    }
+
+    /// Writes the encoded blob to `w`, e.g. a [`std::fs::File`].
+    pub fn write_to<W: Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(&self.to_bytes())
+    }
+
+    /// Reads and decodes a blob previously written by [`MachineState::write_to`].
+    pub fn read_from<R: Read>(mut r: R) -> io::Result<Self> {
+        let mut bytes = Vec::with_capacity(Self::ENCODED_LEN);
+        r.read_to_end(&mut bytes)?;
+        Self::from_bytes(&bytes)
+    }
+}
@@ -1,5 +1,6 @@
-use std::{sync::{Arc,Mutex}, thread::{spawn, sleep}, time::Duration};
+use std::{sync::{Arc,Mutex,mpsc::{channel, Receiver, Sender}}, thread::{spawn, sleep}, time::Duration};
 
+use super::audio::AudioCmd;
 
 /// Timers
 pub struct Timer {
@@ -9,6 +10,9 @@ pub struct Timer {
     pub stime: Arc<Mutex<u8>>,
     /// is beeping
     pub beep: Arc<Mutex<bool>>,
+    /// XO-CHIP audio command listeners, notified whenever the sound timer's
+    /// playing state flips, and whenever the pattern/pitch registers change.
+    audio_changes: Arc<Mutex<Vec<Sender<AudioCmd>>>>,
 }
 
 impl Timer {
@@ -18,6 +22,7 @@ impl Timer {
             dtime: Arc::new(Mutex::new(0)),
             stime: Arc::new(Mutex::new(0)),
             beep: Arc::new(Mutex::new(false)),
+            audio_changes: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -31,12 +36,30 @@ impl Timer {
         let mut stime_access = self.stime.lock().unwrap();
         let mut beep_access = self.beep.lock().unwrap();
 
-        if *stime_access > 0 {
+        let playing = *stime_access > 0;
+        if playing {
             *stime_access -= 1;
-            *beep_access = true;
-        } else {
-            *beep_access = false;
         }
+        if *beep_access != playing {
+            self.send_audio(AudioCmd::Playing(playing));
+        }
+        *beep_access = playing;
+    }
+
+    /// Broadcasts an audio command to every receiver obtained through
+    /// [`Timer::get_audio_changes_pipe`].
+    pub fn send_audio(&self, cmd: AudioCmd) {
+        for tx in self.audio_changes.lock().unwrap().iter() {
+            let _ = tx.send(cmd.clone());
+        }
+    }
+
+    /// Subscribes to XO-CHIP audio commands, analogous to
+    /// [`super::display::Display::get_changes_pipe`].
+    pub fn get_audio_changes_pipe(&self) -> Receiver<AudioCmd> {
+        let (tx, rx) = channel::<AudioCmd>();
+        self.audio_changes.lock().unwrap().push(tx);
+        rx
     }
 
     pub fn start(mut self, delay: Duration) {
@@ -55,6 +78,7 @@ impl Clone for Timer {
             dtime: self.dtime.clone(),
             stime: self.stime.clone(),
             beep: self.beep.clone(),
+            audio_changes: self.audio_changes.clone(),
         }
     }
 }
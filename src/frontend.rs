@@ -23,12 +23,16 @@ use crate::emu::display::DisplayCmd;
 #[cfg(feature = "tui")]
 mod term;
 
+#[cfg(feature = "tui")]
+pub use term::{BellStyle, KeyCode, KeyEvent, Modifiers};
+
 //const FPS: u8 = 60;
 //const FRAME_DELAY: Duration = Duration::from_nanos((1_000_000_000f64/FPS as f64) as u64);
 
-pub fn init(display_changes: Receiver<DisplayCmd>, keyboard_sender: Sender<[u8; 4]>, beep: Arc<Mutex<bool>>) {
+#[cfg(feature = "tui")]
+pub fn init(display_changes: Receiver<DisplayCmd>, keyboard_sender: Sender<KeyEvent>, beep: Arc<Mutex<bool>>, bell_style: BellStyle) {
 	#[cfg(feature = "tui")]
-	term::init(display_changes, keyboard_sender.clone(), beep);
+	term::init(display_changes, keyboard_sender.clone(), beep, bell_style);
 }
 
 pub fn exit() {
@@ -16,41 +16,133 @@
     along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
- use std::{cmp::min, io::{self, Error, ErrorKind::Interrupted, Read, Write, stdin, stdout}, sync::{Arc, Mutex, mpsc::{Receiver, Sender}}, thread::{self, sleep}, time::Duration};
+ use std::{cmp::min, io::{self, Error, ErrorKind::Interrupted, Read, Write, stdin, stdout}, sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}, mpsc::{Receiver, Sender}}, thread::{self, sleep}, time::Duration};
 
-#[cfg(target_os = "windows")]
+#[cfg(windows)]
 mod consoleapi;
+mod input;
 
 use crate::emu::display::DisplayCmd;
+pub use input::{KeyCode, KeyEvent, Modifiers};
 
-#[cfg(target_os = "windows")]
+/// How the sound timer's "bell" is surfaced to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BellStyle {
+	/// Write `\x07` while the bell is active. The default, but useless on
+	/// terminals that mute or ignore it.
+	#[default]
+	Audible,
+	/// Invert the screen (DECSCNM) for as long as the bell is active instead
+	/// of making a sound.
+	Visible,
+	/// Don't surface the bell at all.
+	None,
+}
+
+/// Snapshot of the console's original mode, restored when dropped so a normal
+/// exit, a panic, or the `ESC` shortcut in `main.rs` never leaves the user's
+/// terminal stuck in raw mode.
+#[cfg(windows)]
+struct RawMode {
+	stdin_mode: consoleapi::DWORD,
+	stdout_mode: consoleapi::DWORD,
+}
+
+#[cfg(windows)]
+impl Drop for RawMode {
+	fn drop(&mut self) {
+		use consoleapi::*;
+		use std::os::windows::io::AsRawHandle;
+
+		unsafe {
+			SetConsoleMode(stdin().as_raw_handle(), self.stdin_mode);
+			SetConsoleMode(stdout().as_raw_handle(), self.stdout_mode);
+		}
+	}
+}
+
+#[cfg(unix)]
+struct RawMode {
+	original: libc::termios,
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+	fn drop(&mut self) {
+		use libc::{tcsetattr, TCSANOW};
+		use std::os::unix::io::AsRawFd;
+
+		unsafe {
+			tcsetattr(stdin().as_raw_fd(), TCSANOW, &self.original);
+		}
+	}
+}
+
+/// The currently active raw-mode guard, held for the lifetime of the program
+/// and torn down by [`restore_terminal`].
+static RAW_MODE: Mutex<Option<RawMode>> = Mutex::new(None);
+
+/// Set once [`restore_terminal`] has run, so a panic racing a normal
+/// [`exit`] (or a second panic during unwinding) doesn't restore twice.
+static RESTORED: AtomicBool = AtomicBool::new(false);
+
+/// Drops the raw-mode guard and leaves the alternate screen buffer. Safe to
+/// call more than once: every caller but the first is a no-op.
+fn restore_terminal() {
+	if RESTORED.swap(true, Ordering::SeqCst) {
+		return;
+	}
+
+	RAW_MODE.lock().unwrap().take();
+	print!("\x1b[?1049l\x1b[?25h");
+	let _ = io::stdout().flush();
+}
+
+/// Chains a restore of the terminal in front of the default panic hook, so a
+/// panic anywhere after [`console_init`] doesn't leave the console stuck in
+/// raw mode with the cursor hidden, even if the caller never reaches
+/// [`exit`].
+fn install_panic_hook() {
+	let default_hook = std::panic::take_hook();
+	std::panic::set_hook(Box::new(move |info| {
+		restore_terminal();
+		default_hook(info);
+	}));
+}
+
+#[cfg(windows)]
 fn console_init() {
-	
+
 	use consoleapi::*;
 	use std::os::windows::io::AsRawHandle;
 
-	
+
 	let h_stdin = stdin().as_raw_handle();
 	let h_stdout = stdout().as_raw_handle();
 
 	unsafe {
 
-		let mode: LPDWORD = &mut 0;
-		if GetConsoleMode(h_stdin, mode) == 0 {
+		let stdin_mode: LPDWORD = &mut 0;
+		if GetConsoleMode(h_stdin, stdin_mode) == 0 {
 			//panic!("Failed to get the mode of the stdin console: {}", GetLastError());
 		}
+		let stdin_mode = *stdin_mode;
 
-		let mode: DWORD = *mode & !(ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT | ENABLE_PROCESSED_INPUT) | (/*ENABLE_WINDOW_INPUT | */ENABLE_VIRTUAL_TERMINAL_INPUT);
-		if SetConsoleMode(h_stdin, mode) == 0 {
-			//panic!("Failed to set the mode of the stdin console: {}", GetLastError());
+		let stdout_mode: LPDWORD = &mut 0;
+		if GetConsoleMode(h_stdout, stdout_mode) == 0 {
+			//panic!("Failed to get the mode of the stdout console: {}", GetLastError());
 		}
+		let stdout_mode = *stdout_mode;
 
-		let mode: LPDWORD = &mut 0;
-		if GetConsoleMode(h_stdout, mode) == 0 {
-			//panic!("Failed to get the mode of the stdout console: {}", GetLastError());
+		*RAW_MODE.lock().unwrap() = Some(RawMode { stdin_mode, stdout_mode });
+		install_panic_hook();
+
+		let mode: DWORD = stdin_mode & !(ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT | ENABLE_PROCESSED_INPUT) | (/*ENABLE_WINDOW_INPUT | */ENABLE_VIRTUAL_TERMINAL_INPUT);
+		if SetConsoleMode(h_stdin, mode) == 0 {
+			//panic!("Failed to set the mode of the stdin console: {}", GetLastError());
 		}
 
-		let mode: DWORD = *mode & !(ENABLE_WRAP_AT_EOL_OUTPUT) | (ENABLE_VIRTUAL_TERMINAL_PROCESSING);
+		let mode: DWORD = stdout_mode & !(ENABLE_WRAP_AT_EOL_OUTPUT) | (ENABLE_VIRTUAL_TERMINAL_PROCESSING);
 		if SetConsoleMode(h_stdout, mode) == 0 {
 			//panic!("Failed to set the mode of the stdout console: {}", GetLastError());
 		}
@@ -58,66 +150,209 @@ fn console_init() {
 
 	print!("\x1b[?1049h\x1b[?25l\x1b]0;Chip-8 Emulator\x07\x1b[;H");
 
-	render_ui();
+	let mut out = io::stdout().lock();
+	refresh_layout(&mut out);
+	out.flush().unwrap();
+}
+
+#[cfg(unix)]
+fn console_init() {
+	use libc::{tcgetattr, tcsetattr, termios, ICANON, ECHO, ISIG, IEXTEN, ICRNL, IXON, OPOST, VMIN, VTIME, TCSANOW};
+	use std::{mem::MaybeUninit, os::unix::io::AsRawFd};
+
+	let fd_stdin = stdin().as_raw_fd();
 
-	io::stdout().flush().unwrap();
+	unsafe {
+		let mut original = MaybeUninit::<termios>::uninit();
+		if tcgetattr(fd_stdin, original.as_mut_ptr()) != 0 {
+			//panic!("Failed to get the termios state of stdin: {}", io::Error::last_os_error());
+		}
+		let original = original.assume_init();
+
+		let mut term = original;
+		term.c_lflag &= !(ICANON | ECHO | ISIG | IEXTEN);
+		term.c_iflag &= !(ICRNL | IXON);
+		term.c_oflag &= !OPOST;
+		term.c_cc[VMIN] = 1;
+		term.c_cc[VTIME] = 0;
+
+		*RAW_MODE.lock().unwrap() = Some(RawMode { original });
+		install_panic_hook();
+
+		if tcsetattr(fd_stdin, TCSANOW, &term) != 0 {
+			//panic!("Failed to set the termios state of stdin: {}", io::Error::last_os_error());
+		}
+	}
+
+	print!("\x1b[?1049h\x1b[?25l\x1b]0;Chip-8 Emulator\x07\x1b[;H");
+
+	let mut out = io::stdout().lock();
+	refresh_layout(&mut out);
+	out.flush().unwrap();
+}
+
+/// Width/height of the 64x32 play area once doubled up for square-ish cells,
+/// plus the width/height of the box drawn around it.
+const PLAY_WIDTH: u16 = 128;
+const PLAY_HEIGHT: u16 = 32;
+const FRAME_WIDTH: u16 = PLAY_WIDTH + 2;
+const FRAME_HEIGHT: u16 = PLAY_HEIGHT + 2;
+
+/// How the play area is currently laid out in the console window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+	/// Not measured yet.
+	Unknown,
+	/// The window is smaller than `FRAME_WIDTH`x`FRAME_HEIGHT`.
+	TooSmall,
+	/// The play area is centered at this `(x, y)` offset.
+	Fits { x: u16, y: u16 },
+}
+
+/// The layout computed from the last known window size, used to detect
+/// resizes and to translate cell coordinates into screen coordinates.
+static LAYOUT: Mutex<Layout> = Mutex::new(Layout::Unknown);
+
+#[cfg(windows)]
+fn terminal_size() -> Option<(u16, u16)> {
+	use consoleapi::*;
+	use std::{mem::MaybeUninit, os::windows::io::AsRawHandle};
+
+	unsafe {
+		let mut info = MaybeUninit::<CONSOLE_SCREEN_BUFFER_INFO>::uninit();
+		if GetConsoleScreenBufferInfo(stdout().as_raw_handle(), info.as_mut_ptr()) == 0 {
+			return None;
+		}
+		let info = info.assume_init();
+		let width = (info.srWindow.right - info.srWindow.left + 1) as u16;
+		let height = (info.srWindow.bottom - info.srWindow.top + 1) as u16;
+		Some((width, height))
+	}
 }
 
-fn render_ui() {
-	println!("\x1b[2J\u{250C}{}\u{2510}", "\u{2500}".repeat(128));
-	for _ in 0..32 {
-		println!("\u{2502}{}\u{2502}", " ".repeat(128));
+#[cfg(unix)]
+fn terminal_size() -> Option<(u16, u16)> {
+	use libc::{ioctl, winsize, TIOCGWINSZ};
+	use std::{mem::MaybeUninit, os::unix::io::AsRawFd};
+
+	unsafe {
+		let mut size = MaybeUninit::<winsize>::uninit();
+		if ioctl(stdout().as_raw_fd(), TIOCGWINSZ, size.as_mut_ptr()) != 0 {
+			return None;
+		}
+		let size = size.assume_init();
+		Some((size.ws_col, size.ws_row))
 	}
-	println!("\u{2514}{}\u{2518}", "\u{2500}".repeat(128));
-	io::stdout().flush().unwrap();
 }
 
-fn render_change(change: DisplayCmd) {
+fn compute_layout(cols: u16, rows: u16) -> Layout {
+	if cols < FRAME_WIDTH || rows < FRAME_HEIGHT {
+		Layout::TooSmall
+	} else {
+		Layout::Fits { x: (cols - FRAME_WIDTH) / 2, y: (rows - FRAME_HEIGHT) / 2 }
+	}
+}
+
+fn draw_border(out: &mut impl Write, x: u16, y: u16) {
+	write!(out, "\x1b[2J\x1b[{};{}H\u{250C}{}\u{2510}", y + 1, x + 1, "\u{2500}".repeat(PLAY_WIDTH as usize)).unwrap();
+	for row in 0..PLAY_HEIGHT {
+		write!(out, "\x1b[{};{}H\u{2502}{}\u{2502}", y + 2 + row, x + 1, " ".repeat(PLAY_WIDTH as usize)).unwrap();
+	}
+	write!(out, "\x1b[{};{}H\u{2514}{}\u{2518}", y + 2 + PLAY_HEIGHT, x + 1, "\u{2500}".repeat(PLAY_WIDTH as usize)).unwrap();
+}
+
+fn draw_too_small(out: &mut impl Write) {
+	write!(out, "\x1b[2J\x1b[1;1Hterminal too small").unwrap();
+}
+
+/// Re-measure the console window and redraw the border (or the "too small"
+/// message) whenever the layout changed since the last frame. There's no
+/// `SIGWINCH`/console-resize-event plumbing here, so this is polled once per
+/// rendered frame instead, which is cheap enough at 60 Hz.
+fn refresh_layout(out: &mut impl Write) -> Layout {
+	let layout = terminal_size().map_or(Layout::TooSmall, |(cols, rows)| compute_layout(cols, rows));
+
+	let mut current = LAYOUT.lock().unwrap();
+	if *current != layout {
+		*current = layout;
+		match layout {
+			Layout::Fits { x, y } => draw_border(out, x, y),
+			Layout::TooSmall | Layout::Unknown => draw_too_small(out),
+		}
+	}
+	layout
+}
+
+fn render_change(out: &mut impl Write, origin: (u16, u16), change: DisplayCmd) {
+	let (origin_x, origin_y) = origin;
 	if let DisplayCmd::Change(data, x, y) = change {
 		for (index, row) in data.iter().enumerate() {
-			print!("\x1b[{};{}H", y+index as u8+2 as u8, x as u8*2+2);
+			write!(out, "\x1b[{};{}H", origin_y + y as u16 + index as u16 + 2, origin_x + x as u16 * 2 + 2).unwrap();
 			let mut mask = 0b1000_0000u8;
 			let end = min(64 - x, 8);
 			for _ in 0..end {
 				if row & mask == 0 {
-					print!("  ");
+					write!(out, "  ").unwrap();
 				} else {
-					print!("\u{258D}\u{258D}");
+					write!(out, "\u{258D}\u{258D}").unwrap();
 				}
 				mask >>= 1;
 			}
 		}
 	};
 
-	print!("\x1b[0;0H");
-	io::stdout().flush().unwrap();
+	write!(out, "\x1b[0;0H").unwrap();
 }
 
 fn render_changes(display_cmds: Receiver<DisplayCmd>) {
 	thread::spawn(move || {
 		loop {
-			let cmd = match display_cmds.recv() {
+			// Collect every change that is already queued up into one logical
+			// frame instead of flushing each `DisplayCmd` on its own.
+			let first = match display_cmds.recv() {
 				Ok(cmd) => cmd,
 				Err(_) => break,
 			};
-			match cmd {
-				DisplayCmd::Change(_, _, _) => render_change(cmd),
-				DisplayCmd::Clear => (),
+			let mut frame = vec![first];
+			while let Ok(cmd) = display_cmds.try_recv() {
+				frame.push(cmd);
 			}
+
+			// Holding stdout's own lock for the whole frame is what keeps the
+			// bell thread's `\x07` from landing in the middle of it; the DEC
+			// synchronized-update markers are additionally ignored by
+			// terminals that don't support them, so this degrades gracefully.
+			let mut out = stdout().lock();
+			write!(out, "\x1b[?2026h").unwrap();
+			if let Layout::Fits { x, y } = refresh_layout(&mut out) {
+				for cmd in frame {
+					match cmd {
+						DisplayCmd::Change(_, _, _) => render_change(&mut out, (x, y), cmd),
+						DisplayCmd::Clear => (),
+					}
+				}
+			}
+			write!(out, "\x1b[?2026l").unwrap();
+			out.flush().unwrap();
 		}
 	});
 }
 
-fn keyboard_init(sender: Sender<[u8; 4]>) {
+fn keyboard_init(sender: Sender<KeyEvent>) {
 	thread::spawn(move || {
 		let stdin = stdin();
 		let mut stdin = stdin.lock();
+		let mut parser = input::InputParser::new();
 
 		loop {
-			let mut buf = [0u8; 4];
+			let mut buf = [0u8; 64];
 			match stdin.read(&mut buf) {
-				Ok(_) => {
-					sender.send(buf).unwrap();
+				Ok(n) => {
+					for event in parser.feed(&buf[..n]) {
+						if sender.send(event).is_err() {
+							return;
+						}
+					}
 				},
     			Err(_) => {
 					if Error::last_os_error().kind() == Interrupted {
@@ -130,37 +365,54 @@ fn keyboard_init(sender: Sender<[u8; 4]>) {
 	});
 }
 
-fn bell_init(beep: Arc<Mutex<bool>>) {
+fn bell_init(beep: Arc<Mutex<bool>>, style: BellStyle) {
 	thread::spawn(move || {
+		// Tracks what was last written for `Visible`, so the screen-reverse
+		// sequence is only sent on the frame the bell's state actually flips
+		// instead of on every tick it stays active.
+		let mut flashing = false;
+
 		loop {
-			{
-				let beep_access = beep.lock().unwrap();
-				if *beep_access {
-					print!("\x07");
-					stdout().flush().unwrap();
-				}
+			let beeping = *beep.lock().unwrap();
+			match style {
+				BellStyle::Audible => {
+					if beeping {
+						let mut out = stdout().lock();
+						write!(out, "\x07").unwrap();
+						out.flush().unwrap();
+					}
+				},
+				BellStyle::Visible => {
+					if beeping != flashing {
+						let mut out = stdout().lock();
+						write!(out, "{}", if beeping { "\x1b[?5h" } else { "\x1b[?5l" }).unwrap();
+						out.flush().unwrap();
+						flashing = beeping;
+					}
+				},
+				BellStyle::None => (),
 			}
 			sleep(Duration::from_nanos((1_000_000_000f64/60f64) as u64))
 		}
 	});
 }
 
-pub fn init(changes: Receiver<DisplayCmd>, keyboard_sender: Sender<[u8; 4]>, beep: Arc<Mutex<bool>>) {
-	#[cfg(target_os = "windows")]
+pub fn init(changes: Receiver<DisplayCmd>, keyboard_sender: Sender<KeyEvent>, beep: Arc<Mutex<bool>>, bell_style: BellStyle) {
+	#[cfg(any(windows, unix))]
 	console_init();
-	#[cfg(target_os = "windows")]
+	#[cfg(any(windows, unix))]
 	render_changes(changes);
-	#[cfg(target_os = "windows")]
+	#[cfg(any(windows, unix))]
 	keyboard_init(keyboard_sender);
-	#[cfg(target_os = "windows")]
-	bell_init(beep);
+	#[cfg(any(windows, unix))]
+	bell_init(beep, bell_style);
 }
 
 fn console_exit() {
-	print!("\x1b[?1049l\x1b[?25h");
+	restore_terminal();
 }
 
 pub fn exit() {
-	#[cfg(target_os = "windows")]
+	#[cfg(any(windows, unix))]
 	console_exit();
 }
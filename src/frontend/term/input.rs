@@ -0,0 +1,180 @@
+/// The key of a decoded key press, without its [`Modifiers`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+	Char(char),
+	Function(u8),
+	Up,
+	Down,
+	Left,
+	Right,
+	Home,
+	End,
+	Backspace,
+	Esc,
+}
+
+/// Modifier keys held down alongside a [`KeyCode`], decoded from the CSI
+/// modifier parameter (`value - 1` as a bitfield: bit0 = Shift, bit1 = Alt, bit2 = Ctrl).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+	pub shift: bool,
+	pub alt: bool,
+	pub ctrl: bool,
+}
+
+impl Modifiers {
+	fn from_param(param: u32) -> Self {
+		let bits = param.saturating_sub(1);
+		Self {
+			shift: bits & 0b001 != 0,
+			alt: bits & 0b010 != 0,
+			ctrl: bits & 0b100 != 0,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyEvent {
+	pub code: KeyCode,
+	pub modifiers: Modifiers,
+}
+
+impl KeyEvent {
+	fn plain(code: KeyCode) -> Self {
+		Self { code, modifiers: Modifiers::default() }
+	}
+}
+
+/// Incrementally decodes a raw VT byte stream into [`KeyEvent`]s.
+///
+/// Bytes are appended with [`feed`](InputParser::feed) as they arrive from
+/// `read`; an escape sequence that hasn't fully arrived yet is kept in the
+/// internal buffer until the terminating byte shows up in a later `feed` call.
+#[derive(Default)]
+pub struct InputParser {
+	buffer: Vec<u8>,
+}
+
+impl InputParser {
+	pub fn new() -> Self {
+		Self { buffer: Vec::new() }
+	}
+
+	/// Feed newly read bytes into the parser, returning every key event that
+	/// could be decoded from the buffer so far.
+	pub fn feed(&mut self, bytes: &[u8]) -> Vec<KeyEvent> {
+		self.buffer.extend_from_slice(bytes);
+
+		let mut events = Vec::new();
+		while let Some((event, consumed)) = self.parse_one() {
+			self.buffer.drain(..consumed);
+			if let Some(event) = event {
+				events.push(event);
+			}
+		}
+		events
+	}
+
+	/// Try to decode a single key event from the front of the buffer.
+	/// Returns `None` if the buffer is empty or holds an escape sequence
+	/// that hasn't been fully read yet.
+	fn parse_one(&self) -> Option<(Option<KeyEvent>, usize)> {
+		let first = *self.buffer.first()?;
+
+		if first == 0x1b {
+			// A lone ESC is indistinguishable from the start of a multi-byte
+			// sequence without a blocking read timeout; since terminals write
+			// sequences in one go, an ESC with nothing buffered behind it yet
+			// is treated as a standalone `Esc` press.
+			return match self.buffer.get(1) {
+				None => Some((Some(KeyEvent::plain(KeyCode::Esc)), 1)),
+				Some(b'[') => self.parse_csi(),
+				Some(b'O') => self.parse_ss3(),
+				Some(_) => Some((Some(KeyEvent::plain(KeyCode::Esc)), 1)),
+			};
+		}
+
+		if first == 0x7f {
+			return Some((Some(KeyEvent::plain(KeyCode::Backspace)), 1));
+		}
+
+		if (0x01..=0x1a).contains(&first) {
+			let modifiers = Modifiers { ctrl: true, ..Modifiers::default() };
+			let letter = (b'a' + first - 0x01) as char;
+			return Some((Some(KeyEvent { code: KeyCode::Char(letter), modifiers }), 1));
+		}
+
+		self.parse_utf8_char(first)
+	}
+
+	fn parse_csi(&self) -> Option<(Option<KeyEvent>, usize)> {
+		let terminator_index = self.buffer[2..]
+			.iter()
+			.position(|byte| (0x40..=0x7e).contains(byte))?;
+		let terminator_index = terminator_index + 2;
+		let final_byte = self.buffer[terminator_index];
+		let params: Vec<u32> = self.buffer[2..terminator_index]
+			.split(|byte| *byte == b';')
+			.map(|chunk| std::str::from_utf8(chunk).ok().and_then(|s| s.parse().ok()).unwrap_or(0))
+			.collect();
+
+		let modifiers = params.get(1).copied().map(Modifiers::from_param).unwrap_or_default();
+
+		let code = match final_byte {
+			b'A' => Some(KeyCode::Up),
+			b'B' => Some(KeyCode::Down),
+			b'C' => Some(KeyCode::Right),
+			b'D' => Some(KeyCode::Left),
+			b'H' => Some(KeyCode::Home),
+			b'F' => Some(KeyCode::End),
+			b'~' => match params.first().copied().unwrap_or(0) {
+				1 => Some(KeyCode::Home),
+				4 => Some(KeyCode::End),
+				15 => Some(KeyCode::Function(5)),
+				17 => Some(KeyCode::Function(6)),
+				18 => Some(KeyCode::Function(7)),
+				19 => Some(KeyCode::Function(8)),
+				20 => Some(KeyCode::Function(9)),
+				21 => Some(KeyCode::Function(10)),
+				23 => Some(KeyCode::Function(11)),
+				24 => Some(KeyCode::Function(12)),
+				_ => None,
+			},
+			_ => None,
+		};
+
+		Some((code.map(|code| KeyEvent { code, modifiers }), terminator_index + 1))
+	}
+
+	fn parse_ss3(&self) -> Option<(Option<KeyEvent>, usize)> {
+		let final_byte = *self.buffer.get(2)?;
+		let code = match final_byte {
+			b'P' => KeyCode::Function(1),
+			b'Q' => KeyCode::Function(2),
+			b'R' => KeyCode::Function(3),
+			b'S' => KeyCode::Function(4),
+			_ => return Some((None, 3)),
+		};
+		Some((Some(KeyEvent::plain(code)), 3))
+	}
+
+	fn parse_utf8_char(&self, first: u8) -> Option<(Option<KeyEvent>, usize)> {
+		let len = match first {
+			0x00..=0x7f => 1,
+			0xc0..=0xdf => 2,
+			0xe0..=0xef => 3,
+			0xf0..=0xf7 => 4,
+			_ => 1,
+		};
+
+		if self.buffer.len() < len {
+			return None;
+		}
+
+		let chr = std::str::from_utf8(&self.buffer[..len])
+			.ok()
+			.and_then(|s| s.chars().next());
+
+		Some((chr.map(|chr| KeyEvent::plain(KeyCode::Char(chr))), len))
+	}
+}
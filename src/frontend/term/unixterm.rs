@@ -1,32 +1,130 @@
-use std::{cmp::min, io::{self, Error, ErrorKind::Interrupted, Read, Write, stdin, stdout}, sync::{Arc, Mutex, mpsc::{Receiver, Sender}}, thread::{self, sleep}, time::Duration};
-use termios::Termios;
+use std::{io::{self, Error, ErrorKind::Interrupted, Read, Write, stdin, stdout}, sync::{atomic::{AtomicBool, AtomicI32, Ordering}, Arc, Mutex, mpsc::{Receiver, Sender}}, thread::{self, sleep}, time::Duration};
+use rustix::termios::{tcgetattr, tcsetattr, OptionalActions, Termios};
 use crate::emu::display::DisplayCmd;
 
+mod input;
 mod termios_extended;
 
+use input::Key;
+
+/// The termios state captured by the most recently constructed [`RawGuard`],
+/// read back by the panic hook and the signal handlers installed in
+/// [`RawGuard::new`] since neither can carry the guard itself across to
+/// where they run.
+static ORIGINAL_TERMIOS: Mutex<Option<(Termios, Termios)>> = Mutex::new(None);
+/// Set once the terminal has been restored, so a signal racing a normal
+/// `Drop` (or a panic unwinding past it) can never restore it twice.
+static RESTORED: AtomicBool = AtomicBool::new(false);
+
+/// Write end of the self-pipe the signal handler wakes up the watcher thread
+/// spawned in [`RawGuard::new`] through, `-1` until that thread exists.
+/// `restore_terminal` locks a `Mutex` and does buffered I/O, neither of which
+/// is async-signal-safe, so the handler itself must not call it directly —
+/// only `write(2)`, which is.
+static SIGNAL_WRITE_FD: AtomicI32 = AtomicI32::new(-1);
+
+fn restore_terminal() {
+	if RESTORED.swap(true, Ordering::SeqCst) {
+		return;
+	}
+
+	if let Some((stdin_termios, stdout_termios)) = ORIGINAL_TERMIOS.lock().unwrap().take() {
+		let _ = tcsetattr(stdin(), OptionalActions::Now, &stdin_termios);
+		let _ = tcsetattr(stdout(), OptionalActions::Now, &stdout_termios);
+	}
+
+	print!("\x1b[?1049l\x1b[?25h");
+	let _ = io::stdout().flush();
+}
+
+/// Async-signal-safe: only calls `write(2)` on the self-pipe, leaving the
+/// actual restore to the watcher thread blocked reading the other end.
+extern "C" fn handle_terminating_signal(signal: libc::c_int) {
+	let fd = SIGNAL_WRITE_FD.load(Ordering::SeqCst);
+	if fd >= 0 {
+		let byte = signal as u8;
+		unsafe {
+			libc::write(fd, &byte as *const u8 as *const libc::c_void, 1);
+		}
+	}
+}
+
+/// Captures the original termios for stdin/stdout, applies raw mode, and
+/// restores the originals (re-showing the cursor and leaving the alternate
+/// screen) when dropped, when the process panics, or when it receives
+/// `SIGINT`/`SIGTERM` — so the user's shell is never left stuck in raw mode.
+pub struct RawGuard;
+
+impl RawGuard {
+	fn new() -> Self {
+		use termios_extended::{make_raw, set_fastest_speed};
+
+		let original_stdin = tcgetattr(stdin()).unwrap();
+		let original_stdout = tcgetattr(stdout()).unwrap();
+
+		RESTORED.store(false, Ordering::SeqCst);
+		*ORIGINAL_TERMIOS.lock().unwrap() = Some((original_stdin.clone(), original_stdout.clone()));
+
+		let mut termios_stdin = original_stdin;
+		let mut termios_stdout = original_stdout;
+		make_raw(&mut termios_stdin);
+		make_raw(&mut termios_stdout);
+		set_fastest_speed(&mut termios_stdin).unwrap();
+		set_fastest_speed(&mut termios_stdout).unwrap();
+		tcsetattr(stdin(), OptionalActions::Now, &termios_stdin).unwrap();
+		tcsetattr(stdout(), OptionalActions::Now, &termios_stdout).unwrap();
+
+		let default_hook = std::panic::take_hook();
+		std::panic::set_hook(Box::new(move |info| {
+			restore_terminal();
+			default_hook(info);
+		}));
+
+		let mut pipe_fds = [0 as libc::c_int; 2];
+		unsafe {
+			libc::pipe(pipe_fds.as_mut_ptr());
+		}
+		let [read_fd, write_fd] = pipe_fds;
+		SIGNAL_WRITE_FD.store(write_fd, Ordering::SeqCst);
+
+		// The handler can only `write(2)` a byte (async-signal-safe); this
+		// thread blocks reading it back and does the actual restore from a
+		// normal, non-signal context once it wakes up.
+		thread::spawn(move || {
+			let mut buf = [0u8; 1];
+			let read = unsafe { libc::read(read_fd, buf.as_mut_ptr() as *mut libc::c_void, 1) };
+			if read > 0 {
+				restore_terminal();
+				std::process::exit(1);
+			}
+		});
+
+		unsafe {
+			libc::signal(libc::SIGINT, handle_terminating_signal as libc::sighandler_t);
+			libc::signal(libc::SIGTERM, handle_terminating_signal as libc::sighandler_t);
+		}
+
+		Self
+	}
+}
+
+impl Drop for RawGuard {
+	fn drop(&mut self) {
+		restore_terminal();
+	}
+}
+
 #[inline(always)]
-fn console_init() {
-    use termios::{cfmakeraw,tcsetattr,TCSANOW};
-    use termios_extended::set_fastest_speed;
-    use std::os::unix::io::AsRawFd;
-
-    let fd_stdin = stdin().as_raw_fd();
-    let fd_stdout = stdout().as_raw_fd();
-    let mut termios_stdin = Termios::from_fd(fd_stdin).unwrap();
-    let mut termios_stdout = Termios::from_fd(fd_stdout).unwrap();
-
-    cfmakeraw(&mut termios_stdin);
-    cfmakeraw(&mut termios_stdout);
-    set_fastest_speed(&mut termios_stdin).unwrap();
-    set_fastest_speed(&mut termios_stdout).unwrap();
-    tcsetattr(fd_stdin, TCSANOW, &termios_stdin).unwrap();
-    tcsetattr(fd_stdout, TCSANOW, &termios_stdout).unwrap();
+fn console_init() -> RawGuard {
+	let guard = RawGuard::new();
 
 	print!("\x1b[?1049h\x1b[?25l\x1b]0;Chip-8 Emulator\x07\x1b[;H");
 
 	render_ui();
 
 	io::stdout().flush().unwrap();
+
+	guard
 }
 
 #[inline(always)]
@@ -39,55 +137,153 @@ fn render_ui() {
 	io::stdout().flush().unwrap();
 }
 
-#[inline(always)]
-fn render_change(change: DisplayCmd) {
-	if let DisplayCmd::Change(data, x, y) = change {
-		for (index, row) in data.iter().enumerate() {
-			print!("\x1b[{};{}H", y+index as u8+2 as u8, x as u8*2+2);
-			let mut mask = 0b1000_0000u8;
-			let end = min(64 - x, 8);
-			for _ in 0..end {
-				if row & mask == 0 {
-					print!("  ");
-				} else {
-					print!("\u{258D}\u{258D}");
+/// Width/height of the play area, as a count of Chip-8 pixels. Each pixel
+/// renders as two terminal columns, so doubling `WIDTH` gives the column
+/// offset of a cell. Large enough to grow into XO-CHIP's 128x64 hi-res mode
+/// without changing the model below, just these two constants.
+const WIDTH: usize = 64;
+const HEIGHT: usize = 32;
+
+/// An in-memory model of the play area plus a shadow copy of what's
+/// currently on the real screen, so a frame only has to emit the cells that
+/// changed since the last one instead of redrawing everything every tick.
+struct ScreenBuffer {
+	model: [[bool; WIDTH]; HEIGHT],
+	shown: [[bool; WIDTH]; HEIGHT],
+	/// Set after a `Clear` (and on the very first frame) to force every
+	/// cell to be treated as changed once, rather than diffing against a
+	/// `shown` buffer that doesn't reflect reality yet.
+	full_repaint: bool,
+}
+
+impl ScreenBuffer {
+	fn new() -> Self {
+		Self {
+			model: [[false; WIDTH]; HEIGHT],
+			shown: [[false; WIDTH]; HEIGHT],
+			full_repaint: true,
+		}
+	}
+
+	fn apply(&mut self, cmd: DisplayCmd) {
+		match cmd {
+			DisplayCmd::Change(data, x, y) => {
+				for (index, row) in data.iter().enumerate() {
+					let row_y = y as usize + index;
+					if row_y >= HEIGHT {
+						break;
+					}
+
+					let mut mask = 0b1000_0000u8;
+					for col in 0..8 {
+						let cell_x = x as usize + col;
+						if cell_x >= WIDTH {
+							break;
+						}
+						self.model[row_y][cell_x] = row & mask != 0;
+						mask >>= 1;
+					}
+				}
+			},
+			DisplayCmd::Clear => {
+				self.model = [[false; WIDTH]; HEIGHT];
+				self.full_repaint = true;
+			},
+		}
+	}
+
+	/// Builds a single ANSI string covering every cell that changed since
+	/// the last call, grouping contiguous runs on a row under one cursor
+	/// move, or `None` if nothing changed.
+	fn diff(&mut self) -> Option<String> {
+		let mut out = String::new();
+		let mut any = false;
+
+		for row in 0..HEIGHT {
+			let mut col = 0;
+			while col < WIDTH {
+				if !(self.full_repaint || self.model[row][col] != self.shown[row][col]) {
+					col += 1;
+					continue;
+				}
+
+				let run_start = col;
+				while col < WIDTH && (self.full_repaint || self.model[row][col] != self.shown[row][col]) {
+					col += 1;
+				}
+
+				any = true;
+				out.push_str(&format!("\x1b[{};{}H", row + 2, run_start * 2 + 2));
+				for cell_x in run_start..col {
+					out.push_str(if self.model[row][cell_x] { "\u{258D}\u{258D}" } else { "  " });
+					self.shown[row][cell_x] = self.model[row][cell_x];
 				}
-				mask >>= 1;
 			}
 		}
-	};
 
-	print!("\x1b[0;0H");
-	io::stdout().flush().unwrap();
+		self.full_repaint = false;
+		any.then(|| {
+			out.push_str("\x1b[0;0H");
+			out
+		})
+	}
 }
 
 #[inline(always)]
 fn render_changes(display_cmds: Receiver<DisplayCmd>) {
 	thread::spawn(move || {
+		let mut screen = ScreenBuffer::new();
+
 		loop {
-			let cmd = match display_cmds.recv() {
+			// Collect every command that's already queued up into one
+			// logical frame instead of diffing and flushing per command.
+			let first = match display_cmds.recv() {
 				Ok(cmd) => cmd,
 				Err(_) => break,
 			};
-			match cmd {
-				DisplayCmd::Change(_, _, _) => render_change(cmd),
-				DisplayCmd::Clear => (),
+			screen.apply(first);
+			while let Ok(cmd) = display_cmds.try_recv() {
+				screen.apply(cmd);
 			}
+
+			if let Some(frame) = screen.diff() {
+				let mut out = stdout().lock();
+				out.write_all(frame.as_bytes()).unwrap();
+				out.flush().unwrap();
+			}
+
+			// Caps the redraw rate so a CPU-bound ROM that spams draw calls
+			// can't turn this into an unbounded write storm.
+			sleep(Duration::from_nanos((1_000_000_000f64/60f64) as u64));
 		}
 	});
 }
 
 #[inline(always)]
-fn keyboard_init(sender: Sender<[u8; 4]>) {
+fn keyboard_init(sender: Sender<u8>) {
 	thread::spawn(move || {
 		let stdin = stdin();
 		let mut stdin = stdin.lock();
+		let mut parser = input::InputParser::new();
+		let keymap = input::default_keymap();
 
 		loop {
-			let mut buf = [0u8; 4];
+			let mut buf = [0u8; 64];
 			match stdin.read(&mut buf) {
-				Ok(_) => {
-					sender.send(buf).unwrap();
+				Ok(n) => {
+					for key in parser.feed(&buf[..n]) {
+						match key {
+							Key::Interrupt => {
+								exit();
+								std::process::exit(0);
+							},
+							_ => {
+								if let Some(&hex) = keymap.get(&key) {
+									sender.send(hex).unwrap();
+								}
+							},
+						}
+					}
 				},
     			Err(_) => {
 					if Error::last_os_error().kind() == Interrupted {
@@ -116,20 +312,17 @@ fn bell_init(beep: Arc<Mutex<bool>>) {
 	});
 }
 
+/// Sets up the console and spawns the render/keyboard/bell threads, handing
+/// back the [`RawGuard`] for the caller to hold for the program's lifetime.
 #[inline(always)]
-pub fn init(changes: Receiver<DisplayCmd>, keyboard_sender: Sender<[u8; 4]>, beep: Arc<Mutex<bool>>) {
-	console_init();
+pub fn init(changes: Receiver<DisplayCmd>, keyboard_sender: Sender<u8>, beep: Arc<Mutex<bool>>) -> RawGuard {
+	let guard = console_init();
 	render_changes(changes);
 	keyboard_init(keyboard_sender);
 	bell_init(beep);
-}
-
-#[inline(always)]
-fn console_exit() {
-	print!("\x1b[?1049l\x1b[?25h");
+	guard
 }
 
 pub fn exit() {
-	#[cfg(any(windows, unix))]
-	console_exit();
+	restore_terminal();
 }
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+/// A decoded key press, independent of how many raw bytes it took to arrive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+	Char(char),
+	Function(u8),
+	Up,
+	Down,
+	Left,
+	Right,
+	Escape,
+	/// `Ctrl+C`, kept distinct from [`Key::Char`] so the UI can quit on it
+	/// instead of relying on the terminal's own `SIGINT` handling.
+	Interrupt,
+}
+
+/// Incrementally decodes a raw VT byte stream into [`Key`]s.
+///
+/// Bytes are appended with [`feed`](InputParser::feed) as they arrive from
+/// `read`; an escape sequence that hasn't fully arrived yet is kept in the
+/// internal buffer until the terminating byte shows up in a later `feed` call.
+#[derive(Default)]
+pub struct InputParser {
+	buffer: Vec<u8>,
+}
+
+impl InputParser {
+	pub fn new() -> Self {
+		Self { buffer: Vec::new() }
+	}
+
+	/// Feed newly read bytes into the parser, returning every key that could
+	/// be decoded from the buffer so far.
+	pub fn feed(&mut self, bytes: &[u8]) -> Vec<Key> {
+		self.buffer.extend_from_slice(bytes);
+
+		let mut keys = Vec::new();
+		while let Some((key, consumed)) = self.parse_one() {
+			self.buffer.drain(..consumed);
+			if let Some(key) = key {
+				keys.push(key);
+			}
+		}
+		keys
+	}
+
+	/// Try to decode a single key from the front of the buffer. Returns
+	/// `None` if the buffer is empty or holds an escape sequence that hasn't
+	/// been fully read yet.
+	fn parse_one(&self) -> Option<(Option<Key>, usize)> {
+		let first = *self.buffer.first()?;
+
+		if first == 0x1b {
+			// A lone ESC is indistinguishable from the start of a multi-byte
+			// sequence without a blocking read timeout; since terminals write
+			// sequences in one go, an ESC with nothing buffered behind it yet
+			// is treated as a standalone `Escape` press.
+			return match self.buffer.get(1) {
+				None => Some((Some(Key::Escape), 1)),
+				Some(b'[') => self.parse_csi(),
+				Some(b'O') => self.parse_ss3(),
+				Some(_) => Some((Some(Key::Escape), 1)),
+			};
+		}
+
+		if first == 0x03 {
+			return Some((Some(Key::Interrupt), 1));
+		}
+
+		self.parse_utf8_char(first)
+	}
+
+	fn parse_csi(&self) -> Option<(Option<Key>, usize)> {
+		let terminator_index = self.buffer[2..]
+			.iter()
+			.position(|byte| (0x40..=0x7e).contains(byte))?;
+		let terminator_index = terminator_index + 2;
+		let final_byte = self.buffer[terminator_index];
+		let params = &self.buffer[2..terminator_index];
+
+		let key = match final_byte {
+			b'A' => Some(Key::Up),
+			b'B' => Some(Key::Down),
+			b'C' => Some(Key::Right),
+			b'D' => Some(Key::Left),
+			b'~' => std::str::from_utf8(params)
+				.ok()
+				.and_then(|s| s.split(';').next())
+				.and_then(|n| n.parse().ok())
+				.map(Key::Function),
+			_ => None,
+		};
+
+		Some((key, terminator_index + 1))
+	}
+
+	fn parse_ss3(&self) -> Option<(Option<Key>, usize)> {
+		let final_byte = *self.buffer.get(2)?;
+		let key = match final_byte {
+			b'P' => Key::Function(1),
+			b'Q' => Key::Function(2),
+			b'R' => Key::Function(3),
+			b'S' => Key::Function(4),
+			_ => return Some((None, 3)),
+		};
+		Some((Some(key), 3))
+	}
+
+	fn parse_utf8_char(&self, first: u8) -> Option<(Option<Key>, usize)> {
+		let len = match first {
+			0x00..=0x7f => 1,
+			0xc0..=0xdf => 2,
+			0xe0..=0xef => 3,
+			0xf0..=0xf7 => 4,
+			_ => 1,
+		};
+
+		if self.buffer.len() < len {
+			return None;
+		}
+
+		let chr = std::str::from_utf8(&self.buffer[..len])
+			.ok()
+			.and_then(|s| s.chars().next());
+
+		Some((chr.map(Key::Char), len))
+	}
+}
+
+/// The `1234/qwer/asdf/zxcv` grid mapping keyboard characters to the 16
+/// Chip-8 hex keys.
+pub fn default_keymap() -> HashMap<Key, u8> {
+	HashMap::from([
+		(Key::Char('1'), 0x1), (Key::Char('2'), 0x2), (Key::Char('3'), 0x3), (Key::Char('4'), 0xC),
+		(Key::Char('q'), 0x4), (Key::Char('Q'), 0x4),
+		(Key::Char('w'), 0x5), (Key::Char('W'), 0x5),
+		(Key::Char('e'), 0x6), (Key::Char('E'), 0x6),
+		(Key::Char('r'), 0xD), (Key::Char('R'), 0xD),
+		(Key::Char('a'), 0x7), (Key::Char('A'), 0x7),
+		(Key::Char('s'), 0x8), (Key::Char('S'), 0x8),
+		(Key::Char('d'), 0x9), (Key::Char('D'), 0x9),
+		(Key::Char('f'), 0xE), (Key::Char('F'), 0xE),
+		(Key::Char('z'), 0xA), (Key::Char('Z'), 0xA),
+		(Key::Char('x'), 0x0), (Key::Char('X'), 0x0),
+		(Key::Char('c'), 0xB), (Key::Char('C'), 0xB),
+		(Key::Char('v'), 0xF), (Key::Char('V'), 0xF),
+	])
+}
@@ -1,43 +1,48 @@
-use std::io;
-use termios::{Termios,cfsetspeed};
-
+use rustix::io;
+use rustix::termios::{InputModes, LocalModes, OutputModes, SpecialCodeIndex, Termios};
+
+/// Explicitly clears only the flags this emulator actually depends on
+/// (ICANON/ECHO/ISIG/IEXTEN, ICRNL/IXON, OPOST), so they are visible here
+/// instead of hidden behind a single opaque libc call. Unlike `cfmakeraw`,
+/// it leaves the other input/local flags (e.g. ISTRIP, INLCR, IGNCR, ECHONL)
+/// and the character size/parity bits as they were.
+pub fn make_raw(termios: &mut Termios) {
+	termios.local_modes.remove(LocalModes::ICANON | LocalModes::ECHO | LocalModes::ISIG | LocalModes::IEXTEN);
+	termios.input_modes.remove(InputModes::ICRNL | InputModes::IXON);
+	termios.output_modes.remove(OutputModes::OPOST);
+	termios.special_codes[SpecialCodeIndex::VMIN] = 1;
+	termios.special_codes[SpecialCodeIndex::VTIME] = 0;
+}
+
+/// Sets stdin/stdout to the fastest speed this platform can express.
+///
+/// On Linux [`Termios::set_speed`] takes an arbitrary integer rate and
+/// reaches for the `BOTHER` custom-speed mechanism (`TCGETS2`/`TCSETS2`)
+/// when it isn't one of the standard `Bxxxxxx` enum values; the 4,000,000
+/// requested here is itself the highest standard Linux value. On the other
+/// targets defined below it requests the highest rate the platform's
+/// standard enum defines; targets not listed have no definition.
 #[cfg(target_os = "linux")]
 pub fn set_fastest_speed(termios: &mut Termios) -> io::Result<()> {
-    cfsetspeed(termios, termios::os::linux::B4000000)
+	termios.set_speed(4_000_000)
 }
 
 #[cfg(target_os = "macos")]
 pub fn set_fastest_speed(termios: &mut Termios) -> io::Result<()> {
-    cfsetspeed(termios, termios::os::macos::B230400)
-}
-
-#[cfg(target_os = "freebsd")]
-pub fn set_fastest_speed(termios: &mut Termios) -> io::Result<()> {
-    cfsetspeed(termios, termios::os::freebsd::B921600)
+	termios.set_speed(230_400)
 }
 
-#[cfg(target_os = "openbsd")]
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
 pub fn set_fastest_speed(termios: &mut Termios) -> io::Result<()> {
-    cfsetspeed(termios, termios::os::openbsd::B921600)
+	termios.set_speed(921_600)
 }
 
-#[cfg(target_os = "netbsd")]
+#[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
 pub fn set_fastest_speed(termios: &mut Termios) -> io::Result<()> {
-    cfsetspeed(termios, termios::os::netbsd::B921600)
+	termios.set_speed(921_600)
 }
 
-#[cfg(target_os = "dragonfly")]
+#[cfg(any(target_os = "solaris", target_os = "illumos"))]
 pub fn set_fastest_speed(termios: &mut Termios) -> io::Result<()> {
-    cfsetspeed(termios, termios::os::dragonfly::B230400)
+	termios.set_speed(921_600)
 }
-
-#[cfg(target_os = "solaris")]
-pub fn set_fastest_speed(termios: &mut Termios) -> io::Result<()> {
-    cfsetspeed(termios, termios::os::solaris::B921600)
-}
-
-#[cfg(target_os = "illumos")]
-pub fn set_fastest_speed(termios: &mut Termios) -> io::Result<()> {
-    cfsetspeed(termios, termios::os::illumos::B921600)
-}
-
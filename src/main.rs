@@ -2,12 +2,18 @@ use core::panic;
 use std::env;
 use std::fs::File;
 use std::io::Read;
+#[cfg(feature = "tui")]
+use std::collections::HashMap;
 use std::process::exit;
 use std::sync::mpsc::Sender;
 use std::sync::mpsc::channel;
 use std::thread::spawn;
+#[cfg(feature = "tui")]
+use frontend::{BellStyle, KeyCode, KeyEvent};
 mod emu;
 mod frontend;
+#[cfg(windows)]
+mod util;
 
 fn main() {
     let args: Vec<String> = env::args().skip(1).collect();
@@ -21,105 +27,56 @@ fn main() {
     let mut rom = [0x0u8; 0xE00];
     file.read(&mut rom).unwrap();
 
-    let mut emulator = emu::Chip8Emu::new(rom);
+    let mut emulator = emu::Chip8Emu::new(rom, emu::Quirks::default(), true);
 
     #[cfg(feature = "tui")]
-    frontend::init(emulator.get_screen_changes(), init_keyboard_proxy(emulator.new_keyboard_driver()), emulator.is_beeping());
+    frontend::init(emulator.get_screen_changes(), init_keyboard_proxy(emulator.new_keyboard_driver()), emulator.is_beeping(), BellStyle::default());
 
+    #[cfg(windows)]
+    util::winapi::timeapi::with_timer_resolution(|| emulator.run()).unwrap();
+    #[cfg(not(windows))]
     emulator.run();
 
     #[cfg(feature = "tui")]
     frontend::exit();
 }
 
-fn init_keyboard_proxy(sender: Sender<u8>) -> Sender<[u8; 4]> {
-    let (tx, rx) = channel::<[u8; 4]>();
+/// The current QWERTZ layout (bottom row `yxcv`) mapping keyboard characters to the 16 Chip-8 hex keys.
+#[cfg(feature = "tui")]
+fn default_keymap() -> HashMap<KeyCode, u8> {
+    HashMap::from([
+        (KeyCode::Char('x'), 0x0), (KeyCode::Char('X'), 0x0),
+        (KeyCode::Char('1'), 0x1),
+        (KeyCode::Char('2'), 0x2),
+        (KeyCode::Char('3'), 0x3),
+        (KeyCode::Char('q'), 0x4), (KeyCode::Char('Q'), 0x4),
+        (KeyCode::Char('w'), 0x5), (KeyCode::Char('W'), 0x5),
+        (KeyCode::Char('e'), 0x6), (KeyCode::Char('E'), 0x6),
+        (KeyCode::Char('a'), 0x7), (KeyCode::Char('A'), 0x7),
+        (KeyCode::Char('s'), 0x8), (KeyCode::Char('S'), 0x8),
+        (KeyCode::Char('d'), 0x9), (KeyCode::Char('D'), 0x9),
+        (KeyCode::Char('y'), 0xA), (KeyCode::Char('Y'), 0xA),
+        (KeyCode::Char('c'), 0xB), (KeyCode::Char('C'), 0xB),
+        (KeyCode::Char('4'), 0xC),
+        (KeyCode::Char('r'), 0xD), (KeyCode::Char('R'), 0xD),
+        (KeyCode::Char('f'), 0xE), (KeyCode::Char('F'), 0xE),
+        (KeyCode::Char('v'), 0xF), (KeyCode::Char('V'), 0xF),
+    ])
+}
+
+#[cfg(feature = "tui")]
+fn init_keyboard_proxy(sender: Sender<u8>) -> Sender<KeyEvent> {
+    let (tx, rx) = channel::<KeyEvent>();
+    let keymap = default_keymap();
     spawn(move || {
         loop {
             match rx.recv() {
-                Ok(data) => {
-                    match data {
-                        [0x58, 0x0, 0x0, 0x0] |
-                        [0x78, 0x0, 0x0, 0x0] => {
-                            sender.send(0x0).unwrap();
-                            ()
-                        },
-                        [0x31, 0x0, 0x0, 0x0] => {
-                            sender.send(0x1).unwrap();
-                            ()
-                        },
-                        [0x32, 0x0, 0x0, 0x0] => {
-                            sender.send(0x2).unwrap();
-                            ()
-                        },
-                        [0x33, 0x0, 0x0, 0x0] => {
-                            sender.send(0x3).unwrap();
-                            ()
-                        },
-                        [0x51, 0x0, 0x0, 0x0] |
-                        [0x71, 0x0, 0x0, 0x0] => {
-                            sender.send(0x4).unwrap();
-                            ()
-                        },
-                        [0x57, 0x0, 0x0, 0x0] |
-                        [0x77, 0x0, 0x0, 0x0] => {
-                            sender.send(0x5).unwrap();
-                            ()
-                        },
-                        [0x45, 0x0, 0x0, 0x0] |
-                        [0x65, 0x0, 0x0, 0x0] => {
-                            sender.send(0x6).unwrap();
-                            ()
-                        },
-                        [0x41, 0x0, 0x0, 0x0] | 
-                        [0x61, 0x0, 0x0, 0x0] => {
-                            sender.send(0x7).unwrap();
-                            ()
-                        },
-                        [0x53, 0x0, 0x0, 0x0] |
-                        [0x73, 0x0, 0x0, 0x0] => {
-                            sender.send(0x8).unwrap();
-                            ()
-                        },
-                        [0x44, 0x0, 0x0, 0x0] |
-                        [0x64, 0x0, 0x0, 0x0] => {
-                            sender.send(0x9).unwrap();
-                            ()
-                        },
-                        [0x59, 0x0, 0x0, 0x0] |
-                        [0x79, 0x0, 0x0, 0x0] => {
-                            sender.send(0xA).unwrap();
-                            ()
-                        },
-                        [0x43, 0x0, 0x0, 0x0] |
-                        [0x63, 0x0, 0x0, 0x0] => {
-                            sender.send(0xB).unwrap();
-                            ()
-                        },
-                        [0x34, 0x0, 0x0, 0x0] => {
-                            sender.send(0xC).unwrap();
-                            ()
-                        },
-                        [0x52, 0x0, 0x0, 0x0] |
-                        [0x72, 0x0, 0x0, 0x0] => {
-                            sender.send(0xD).unwrap();
-                            ()
-                        },
-                        [0x46, 0x0, 0x0, 0x0] |
-                        [0x66, 0x0, 0x0, 0x0] => {
-                            sender.send(0xE).unwrap();
-                            ()
-                        },
-                        [0x56, 0x0, 0x0, 0x0] |
-                        [0x76, 0x0, 0x0, 0x0] => {
-                            sender.send(0xF).unwrap();
-                            ()
-                        },
-                        [0x1b, 0x0, 0x0, 0x0] => {
-                            frontend::exit();
-                            exit(0)
-                        },
-                        _ => (),
+                Ok(event) => {
+                    if event.code == KeyCode::Esc {
+                        frontend::exit();
+                        exit(0);
+                    } else if let Some(&key) = keymap.get(&event.code) {
+                        sender.send(key).unwrap();
                     }
                 },
                 Err(_) => (),
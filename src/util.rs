@@ -0,0 +1,2 @@
+#[cfg(windows)]
+pub mod winapi;